@@ -56,17 +56,24 @@ fn verify_alarm_set<I2C,E>(rtc: &mut RV3028<I2C>, alarm_dt: &NaiveDateTime,
     assert_eq!(match_hour, out_match_hour);
     assert_eq!(match_minute, out_match_minute);
 
-    if weekday.is_some() {
-        // weekday-based alarm
-        assert_eq!(out_weekday, weekday);
-    }
-    else {
-        // date-based alarm
-        assert_eq!(dt.date().day(), alarm_dt.date().day());
+    // unmatched fields are reported as placeholders, so only check matched fields
+    if match_day {
+        if weekday.is_some() {
+            // weekday-based alarm
+            assert_eq!(out_weekday, weekday);
+        }
+        else {
+            // date-based alarm
+            assert_eq!(dt.date().day(), alarm_dt.date().day());
+        }
     }
 
-    assert_eq!(dt.time().hour(), alarm_dt.time().hour());
-    assert_eq!(dt.time().minute(), alarm_dt.time().minute());
+    if match_hour {
+        assert_eq!(dt.time().hour(), alarm_dt.time().hour());
+    }
+    if match_minute {
+        assert_eq!(dt.time().minute(), alarm_dt.time().minute());
+    }
 
 }
 
@@ -119,7 +126,6 @@ fn main() {
     verify_alarm_set(&mut rtc, &alarm_dt, None, true, true, true);
     verify_alarm_set(&mut rtc, &alarm_dt, None, true, true, false);
     verify_alarm_set(&mut rtc, &alarm_dt, None, true, false, false);
-    verify_alarm_set(&mut rtc, &alarm_dt, None, false, false, false);
     verify_alarm_set(&mut rtc, &alarm_dt, None, false, false, true);
     verify_alarm_set(&mut rtc, &alarm_dt, None, false, true, true);
     verify_alarm_set(&mut rtc, &alarm_dt, None, false, true, false);
@@ -129,12 +135,17 @@ fn main() {
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Mon), true, true, true);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Tue), true, true, false);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Wed), true, false, false);
-    verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Thu), false, false, false);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Fri), false, false, true);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Sat), false, true, true);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Sun), false, true, false);
     verify_alarm_set(&mut rtc, &alarm_dt, Some(Weekday::Mon), true, false, true);
 
+    // An alarm that matches nothing fires every minute, so it must be set explicitly
+    rtc.set_alarm_every_minute().unwrap();
+    let every_minute = rtc.get_alarm_config().unwrap();
+    assert!(every_minute.minute.is_none() && every_minute.hour.is_none());
+    assert!(every_minute.weekday_or_date.is_none());
+
     // Now, prep for alarm output on INT pin in (less than) 60 seconds
     let _ = rtc.clear_all_int_out_bits();
 
@@ -191,7 +202,10 @@ fn main() {
         }
     }
     _ = rtc.clear_all_int_out_bits();
-    _ = rtc.check_and_clear_alarm();
+    // clear any remaining flags, releasing INT
+    if let Ok(fired) = rtc.drain_and_report() {
+        println!("flags cleared at exit: {:?}", fired);
+    }
 
 
 }
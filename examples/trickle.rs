@@ -35,9 +35,11 @@ fn main() {
     println!("start sys {}\r\nrtc1 {}\r\n", sys_dt, dt1);
 
     // enable trickle charging
-    let one_enabled = rtc1.toggle_trickle_charge(
+    let (one_enabled, one_limiter) = rtc1.config_trickle_charge(
         true, TrickleChargeCurrentLimiter::Ohms15k).unwrap();
-    println!("rtc1 trickle enabled: {}",one_enabled);
+    println!("rtc1 trickle enabled: {} limiter: {:?}",one_enabled, one_limiter);
+    assert!(one_enabled);
+    assert_eq!(one_limiter, TrickleChargeCurrentLimiter::Ohms15k);
 
     // enable switchover to backup power (Vbackup)
     let bsm_enabled = rtc1.toggle_backup_switchover(true).unwrap();
@@ -1,7 +1,9 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 
 
+#[cfg(feature = "chrono")]
 pub use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+#[cfg(feature = "chrono")]
 pub use rtcc::{  DateTimeAccess };
 
 use embedded_hal::blocking::i2c::{Write, Read, WriteRead};
@@ -12,7 +14,7 @@ const RV3028_ADDRESS: u8 = 0xA4 >> 1;
 // Main time register addresses
 const REG_SECONDS: u8 = 0x00;
 // const REG_MINUTES: u8 = 0x01;
-// const REG_HOURS: u8 = 0x02;
+const REG_HOURS: u8 = 0x02;
 
 
 // Holds the current day of the week.
@@ -100,6 +102,7 @@ const REG_CLOCK_INTERRUPT_MASK:u8 = 0x12;
 const REG_EVENT_CONTROL: u8 = 0x13;
 
 // Time Stamp function registers (Event Logging)
+#[cfg(feature = "chrono")]
 const REG_COUNT_EVENTS_TS: u8 = 0x14; // Count TS
 // const REG_SECONDS_TS: u8 = 0x15; // Seconds TS
 // const REG_MINUTES_TS: u8 = 0x16; // Minutes TS
@@ -115,6 +118,13 @@ const REG_UNIX_TIME_0: u8 = 0x1B;
 // const REG_UNIX_TIME_2: u8 = 0x1D;
 // const REG_UNIX_TIME_3: u8 = 0x1E;
 
+// First address of the two bytes of general purpose User RAM
+const REG_USER_RAM1: u8 = 0x1F;
+const REG_USER_RAM2: u8 = 0x20;
+
+// Value kept in REG_USER_RAM2 for power loss detection
+const POWER_LOSS_MAGIC: u8 = 0xA5;
+
 // REG_CONTROL1 "Control 1" register bits:
 #[repr(u8)]
 enum RegControl1Bits {
@@ -124,7 +134,8 @@ enum RegControl1Bits {
   WadaBit = 1 << 5,
   //  USEL / Update Interrupt Select bit. Seconds or minutes.
   UselBit = 1 << 4,
-  //  EERD_BIT: u8 = 1 << 3,
+  // EERD / EEPROM Memory Refresh Disable bit
+  EepromRefreshDisableBit = 1 << 3,
   // TE / Periodic Countdown Timer Enable bit.
   TimerEnableBit = 1 << 2,
   // TD / Timer Clock Frequency selection bits
@@ -133,17 +144,35 @@ enum RegControl1Bits {
 
 /// Countown timer clock frequency selector
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum TimerClockFreq {
-  Hertz4096 = 0b00, // 4096 Hz, 244.14 μs period
-  Hertz64 = 0b01, // 64 Hz, 15.625 ms period
-  Hertz1 = 0b10, // 1 Hz, One second period
-  HertzSixtieth = 0b11, // 1/60 Hz, One minute period
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TimerClockFreq {
+  /// 4096 Hz, 244.14 μs period
+  Hertz4096 = 0b00,
+  /// 64 Hz, 15.625 ms period
+  Hertz64 = 0b01,
+  /// 1 Hz, One second period
+  Hertz1 = 0b10,
+  /// 1/60 Hz, One minute period
+  HertzSixtieth = 0b11,
+}
+
+impl TimerClockFreq {
+  // Decode the TD bits of the Control 1 register value
+  fn from_register(control1: u8) -> Self {
+    match control1 & RegControl1Bits::TimerClockFreqBits as u8 {
+      0b00 => TimerClockFreq::Hertz4096,
+      0b01 => TimerClockFreq::Hertz64,
+      0b10 => TimerClockFreq::Hertz1,
+      _ => TimerClockFreq::HertzSixtieth,
+    }
+  }
 }
 
 // REG_STATUS Status register bits:
 #[repr(u8)]
 enum RegStatusBits {
-  //EEBUSY_BIT = 1 << 7,
+  // EEBUSY / EEPROM Memory Busy Status bit
+  EepromBusyBit = 1 << 7,
   // CLKF  / Clock Output Interrupt Flag
   ClockIntFlagBit = 1 << 6,
   // BSF bit
@@ -161,9 +190,26 @@ enum RegStatusBits {
 }
 
 // EEPROM register addresses and commands
+const EEPROM_PW_ENABLE_ADDRESS: u8 = 0x30;// RAM mirror of EEPROM Password Enable (EEPWE)
+const EEPROM_CLKOUT_ADDRESS: u8 = 0x35;// RAM mirror of EEPROM Clkout config value
+const EEPROM_OFFSET_ADDRESS: u8 = 0x36;// RAM mirror of EEPROM Offset (EEOffset[8:1])
 const EEPROM_MIRROR_ADDRESS: u8 = 0x37;// RAM mirror of EEPROM config values
-// const EEPROM_CMD_READ: u8 = 0x00;
-// const EEPROM_CMD_WRITE: u8 = 0x01;
+const REG_EEPROM_ADDRESS: u8 = 0x25; // EEADDR
+const REG_EEPROM_DATA: u8 = 0x26; // EEDATA
+const REG_EEPROM_COMMAND: u8 = 0x27; // EECMD
+const REG_ID: u8 = 0x28; // ID: Hardware ID (HID) and Version ID (VID)
+const EEPROM_CMD_WRITE_ONE: u8 = 0x21; // Write one EEPROM byte
+const EEPROM_CMD_READ_ONE: u8 = 0x22; // Read one EEPROM byte
+// EEPROM address ranges
+const EEPROM_USER_LAST: u8 = 0x2A; // User EEPROM is 0x00..=0x2A
+const EEPROM_CONFIG_FIRST: u8 = 0x30; // Configuration EEPROM is 0x30..=0x37
+const EEPROM_CONFIG_LAST: u8 = 0x37;
+
+/// Bytes per slot of a wear-leveled user EEPROM counter: a sequence byte and a `u32` value
+pub const USER_EEPROM_COUNTER_SLOT_SIZE: u8 = 5;
+// The most slots that fit in user EEPROM
+const MAX_USER_EEPROM_COUNTER_SLOTS: usize =
+  (EEPROM_USER_LAST as usize + 1) / USER_EEPROM_COUNTER_SLOT_SIZE as usize;
 
 
 // REG_EVENT_CONTROL Event Control register bits:   EHL, ET, TSR, TSOW, TSS
@@ -184,6 +230,36 @@ enum RegEventControlBits {
 pub const TS_EVENT_SOURCE_EVI: u8 = 0; /// Event log source is external interrupt EVI (default)
 pub const TS_EVENT_SOURCE_BSF: u8 = 1; /// Event log source is backup power switchover
 
+/// Event filtering time (ET bits) for level detection on the EVI pin
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventFilterTime {
+  /// No filtering: edge detection
+  NoFilter = 0b00,
+  /// Level detection with 3.9 ms sampling period
+  Ms3_9 = 0b01,
+  /// Level detection with 15.6 ms sampling period
+  Ms15_6 = 0b10,
+  /// Level detection with 125 ms sampling period
+  Ms125 = 0b11,
+}
+
+/// What triggers an event on the EVI pin.
+/// With no filtering the EVI pin uses edge detection, otherwise level detection:
+/// the EHL bit selects rising or falling edge, or high or low level, accordingly.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventTrigger {
+  /// Rising edge, with no filtering
+  RisingEdge,
+  /// Falling edge, with no filtering
+  FallingEdge,
+  /// High level, with the given filtering time (`NoFilter` is treated as rising edge)
+  HighLevel(EventFilterTime),
+  /// Low level, with the given filtering time (`NoFilter` is treated as falling edge)
+  LowLevel(EventFilterTime),
+}
+
 // REG_CLOCK_INTERRUPT_MASK bits
 #[repr(u8)]
 enum RegClockIntMaskBits {
@@ -212,13 +288,26 @@ enum RegControl2Bits {
   AlarmIntEnableBit = 1 << 3,
   // EIE / Event Interrupt Enable bit
   EventIntEnableBit = 1 << 2,
+  // 12_24 / 12 or 24 hour mode bit: 12 hour mode if set
+  TwelveHourModeBit = 1 << 1,
+}
+
+// EEPROM_CLKOUT_ADDRESS / EEPROM Clkout register bits:
+#[repr(u8)]
+enum RegEepromClkoutBits {
+  // CLKOE / CLKOUT Enable bit -- if 1 (default) then normal clock output
+  ClockoutEnableBit = 1 << 7,
+  // PORIE / Power On Reset Interrupt Enable bit
+  PorIntEnableBit = 1 << 3,
+  // FD / CLKOUT Frequency Selection bits
+  FrequencySelectionBits = 0b111,
 }
 
 // EEPROM_MIRROR_ADDRESS / EEPROM mirror register bits:
 #[repr(u8)]
 enum RegEepromMirrorBits {
-  // CLKOE / CLKOUT Enable bit -- if 1 (default) then normal clock output
-  ClockoutOutputEnableBit = 1 << 7,
+  // EEOffset[0], the LSB of the aging offset: CLKOE is in EEPROM_CLKOUT_ADDRESS
+  OffsetLsbBit = 1 << 7,
   // BCIE / Backup Switchover Interrupt Enable bit bit
   BackupSwitchIntEnableBit = 1 << 6,
   // TCE bit
@@ -229,7 +318,28 @@ enum RegEepromMirrorBits {
   TrickleChargeResistanceBits = 0b11, // TCR bits
 }
 
-#[derive(Clone, Copy)]
+// Register bits that drive the INT and CLKOUT pins, as (register, bits) pairs,
+// see `RV3028::enter_low_power_outputs`
+const LOW_POWER_OUTPUT_BITS: [(u8, u8); 4] = [
+  // CLKIE, UIE, TIE, AIE, EIE
+  (REG_CONTROL2, RegControl2Bits::ClockoutIntEnableBit as u8 |
+    RegControl2Bits::TimeUpdateIntEnableBit as u8 |
+    RegControl2Bits::TimerIntEnableBit as u8 |
+    RegControl2Bits::AlarmIntEnableBit as u8 |
+    RegControl2Bits::EventIntEnableBit as u8),
+  // CEIE, CAIE, CTIE, CUIE
+  (REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnExtEvtBit as u8 |
+    RegClockIntMaskBits::ClockoutOnAlarmBit as u8 |
+    RegClockIntMaskBits::ClockoutOnPctBit as u8 |
+    RegClockIntMaskBits::ClockoutOnUpdateBit as u8),
+  // CLKOE
+  (EEPROM_CLKOUT_ADDRESS, RegEepromClkoutBits::ClockoutEnableBit as u8),
+  // BSIE
+  (EEPROM_MIRROR_ADDRESS, RegEepromMirrorBits::BackupSwitchIntEnableBit as u8),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum TrickleChargeCurrentLimiter {
   Ohms3k = 0b00,
   Ohms5k = 0b01,
@@ -237,9 +347,352 @@ pub enum TrickleChargeCurrentLimiter {
   Ohms15k = 0b11,
 }
 
+impl TrickleChargeCurrentLimiter {
+  // Decode the TCR bits of the EEPROM mirror register value
+  fn from_register(backup: u8) -> Self {
+    match backup & RegEepromMirrorBits::TrickleChargeResistanceBits as u8 {
+      0b00 => TrickleChargeCurrentLimiter::Ohms3k,
+      0b01 => TrickleChargeCurrentLimiter::Ohms5k,
+      0b10 => TrickleChargeCurrentLimiter::Ohms9k,
+      _ => TrickleChargeCurrentLimiter::Ohms15k,
+    }
+  }
+}
+
+/// Frequency of the clock output on the CLKOUT pin (FD bits)
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ClockoutRate {
+  /// 32.768 kHz (default)
+  Hertz32768 = 0b000,
+  /// 8192 Hz
+  Hertz8192 = 0b001,
+  /// 1024 Hz
+  Hertz1024 = 0b010,
+  /// 64 Hz
+  Hertz64 = 0b011,
+  /// 32 Hz
+  Hertz32 = 0b100,
+  /// 1 Hz
+  Hertz1 = 0b101,
+  /// Periodic Countdown Timer interrupt
+  CountdownTimer = 0b110,
+  /// CLKOUT held low
+  Low = 0b111,
+}
+
+impl ClockoutRate {
+  // Decode the FD bits of the EEPROM Clkout register value
+  fn from_register(clkout: u8) -> Self {
+    match clkout & RegEepromClkoutBits::FrequencySelectionBits as u8 {
+      0b000 => ClockoutRate::Hertz32768,
+      0b001 => ClockoutRate::Hertz8192,
+      0b010 => ClockoutRate::Hertz1024,
+      0b011 => ClockoutRate::Hertz64,
+      0b100 => ClockoutRate::Hertz32,
+      0b101 => ClockoutRate::Hertz1,
+      0b110 => ClockoutRate::CountdownTimer,
+      _ => ClockoutRate::Low,
+    }
+  }
+}
+
+/// Sources of interrupt signals on the INT pin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InterruptSource {
+  /// Alarm Interrupt (AF / AIE)
+  Alarm,
+  /// Periodic Countdown Timer Interrupt (TF / TIE)
+  CountdownTimer,
+  /// Periodic Time Update Interrupt (UF / UIE)
+  TimeUpdate,
+  /// External Event Interrupt on the EVI pin (EVF / EIE)
+  ExternalEvent,
+  /// Automatic Backup Switchover Interrupt (BSF / BSIE)
+  BackupSwitchover,
+  /// Power On Reset Interrupt (PORF / PORIE)
+  PowerOnReset,
+}
+
+/// How the INT pin behaves when an interrupt source fires
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IntOutputMode {
+  /// INT is driven low briefly, then released automatically
+  /// (the status flag remains set until cleared)
+  Pulsed,
+  /// INT is held low until the status flag is cleared
+  Latched,
+}
+
+/// The maximum time representable by the RTC's Unix time counter, 2106-02-07 06:28:15 UTC.
+/// The counter wraps to zero after this.
+pub const MAX_UNIX_TIME: u32 = u32::MAX;
+
+/// Whether the Unix time counter must be read twice (see `RV3028::get_unix_time_blocking`).
+/// The application manual recommends the read-twice for every silicon revision: the four
+/// counter bytes aren't latched during a read, so an increment between the byte reads
+/// can tear the value. This isn't an erratum fixed by a later Version ID (VID),
+/// so it doesn't depend on `RV3028::get_device_id`.
+pub const UNIX_READ_WORKAROUND_REQUIRED: bool = true;
+
+/// Default maximum number of EEBUSY polls while waiting for the EEPROM,
+/// see `RV3028::set_eeprom_poll_limit`
+pub const DEFAULT_EEPROM_POLL_LIMIT: u32 = 2000;
+
+// Maximum number of reads while waiting for a multi-register value to hold still
+// between two successive reads, before giving up with `Error::Timeout`
+const MAX_CONSISTENT_READS: u32 = 10;
+
+// Seconds in a day, for wrapping elapsed times of day
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
 // Special alarm register value
 const ALARM_NO_WATCH_FLAG: u8 = 1 <<  7;
 
+// AMPM bit of the hours registers, only used in 12 hour mode: set for PM hours
+const HOURS_PM_FLAG: u8 = 1 << 5;
+
+/// Errors returned by methods that validate their inputs
+/// in addition to accessing the RTC over i2c
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Error<E> {
+  /// Underlying i2c bus error
+  I2c(E),
+  /// A value is outside the range supported by the RTC registers
+  OutOfRange,
+  /// The RTC registers do not hold a valid date or time
+  InvalidDateTime,
+  /// A value read back from the RTC doesn't match the value written
+  VerifyFailed,
+  /// The RTC stayed busy, or its registers kept changing, for longer than allowed
+  Timeout,
+  /// The alarm settings don't match any field, so the alarm would fire every minute
+  AlarmMatchesNothing,
+}
+
+impl<E> From<E> for Error<E> {
+  fn from(err: E) -> Self {
+    Error::I2c(err)
+  }
+}
+
+
+/// Alarm settings as stored in the alarm registers.
+/// Each field is either `Some(value)` if the alarm matches that value,
+/// or `None` if that field is ignored when matching.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AlarmConfig {
+  /// Minute to match, 0..59
+  pub minute: Option<u8>,
+  /// Hour to match, 0..23
+  pub hour: Option<u8>,
+  /// Weekday (0..6) for a weekday alarm, or date of month (1..31) for a date alarm
+  pub weekday_or_date: Option<u8>,
+  /// Whether this is a weekday alarm (WADA = 0) rather than a date alarm
+  pub is_weekday: bool,
+}
+
+/// Periodic time update settings, see `RV3028::set_time_update_config`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TimeUpdateConfig {
+  /// Update every minute (USEL = 1) rather than every second
+  pub minute_interval: bool,
+  /// Whether the update asserts the INT pin (UIE)
+  pub interrupt_enabled: bool,
+  /// Whether the update enables the clock output on CLKOUT (CUIE)
+  pub clockout_on_update: bool,
+}
+
+/// Which interrupt sources drive the INT pin, see `RV3028::set_interrupt_enables`.
+/// The Power On Reset interrupt (PORIE) is configured in EEPROM and isn't included.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InterruptEnables {
+  /// Periodic Time Update Interrupt (UIE)
+  pub time_update: bool,
+  /// Periodic Countdown Timer Interrupt (TIE)
+  pub countdown_timer: bool,
+  /// Alarm Interrupt (AIE)
+  pub alarm: bool,
+  /// External Event Interrupt on the EVI pin (EIE)
+  pub external_event: bool,
+  /// Automatic Backup Switchover Interrupt (BSIE)
+  pub backup_switchover: bool,
+}
+
+/// Configuration that affects the RTC's current draw, see `RV3028::active_power_features`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PowerFeatures {
+  /// Whether the CLKOUT pin is enabled (CLKOE)
+  pub clockout_enabled: bool,
+  /// Whether the clock output is enabled by interrupts (CLKIE)
+  pub clockout_interrupt_controlled: bool,
+  /// Clock output frequency (FD)
+  pub clockout_rate: ClockoutRate,
+  /// Whether trickle charging of the backup supply is enabled (TCE)
+  pub trickle_charge_enabled: bool,
+  /// Trickle charge current limiter (TCR)
+  pub trickle_charge_limiter: TrickleChargeCurrentLimiter,
+  /// Whether backup switchover is enabled (BSM)
+  pub backup_switchover_enabled: bool,
+  /// Interrupt sources that drive the INT pin
+  pub interrupts: InterruptEnables,
+}
+
+/// Contents of the event log (the Time Stamp function registers)
+#[cfg(feature = "chrono")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EventLog {
+  /// Number of events logged since the log was last reset
+  pub count: u32,
+  /// Timestamp of one logged event, if any events have been logged
+  pub timestamp: Option<NaiveDateTime>,
+  /// Whether `timestamp` is of the most recent event (TSOW = 1),
+  /// rather than the first event since the log was reset (TSOW = 0)
+  pub is_latest: bool,
+  /// Source of the logged events (TSS), eg `TS_EVENT_SOURCE_EVI` or `TS_EVENT_SOURCE_BSF`
+  pub source: u8,
+}
+
+/// Oscillator compensation settings, see `RV3028::get_oscillator_status`
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OscillatorStatus {
+  /// Aging offset (EEOffset), in steps of about 0.9537 ppm, -256..255.
+  /// Positive values speed up the clock.
+  pub aging_offset: i16,
+}
+
+/// Interrupt flags of the Status register
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StatusFlags {
+  /// CLKF / Clock Output Interrupt Flag
+  pub clock_output: bool,
+  /// BSF / Backup Switch Flag
+  pub backup_switch: bool,
+  /// UF / Periodic Time Update Flag
+  pub time_update: bool,
+  /// TF / Periodic Countdown Timer Flag
+  pub countdown_timer: bool,
+  /// AF / Alarm Flag
+  pub alarm: bool,
+  /// EVF / Event Flag
+  pub external_event: bool,
+  /// PORF / Power On Reset Flag
+  pub power_on_reset: bool,
+}
+
+impl StatusFlags {
+  // Decode the flags from the Status register value
+  fn from_register(status: u8) -> Self {
+    StatusFlags {
+      clock_output: 0 != status & RegStatusBits::ClockIntFlagBit as u8,
+      backup_switch: 0 != status & RegStatusBits::BackupSwitchFlag as u8,
+      time_update: 0 != status & RegStatusBits::TimeUpdateFlag as u8,
+      countdown_timer: 0 != status & RegStatusBits::PeriodicTimerFlag as u8,
+      alarm: 0 != status & RegStatusBits::AlarmFlagBit as u8,
+      external_event: 0 != status & RegStatusBits::EventFlagBit as u8,
+      power_on_reset: 0 != status & RegStatusBits::PowerOnResetFlagBit as u8,
+    }
+  }
+
+  /// Whether any flag is set
+  pub fn any(&self) -> bool {
+    self.clock_output || self.backup_switch || self.time_update || self.countdown_timer
+      || self.alarm || self.external_event || self.power_on_reset
+  }
+}
+
+/// Decoded Control 1 and Control 2 register settings, see `RV3028::get_control_config`
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ControlConfig {
+  /// TRPT / Whether the Periodic Countdown Timer repeats
+  pub timer_repeating: bool,
+  /// WADA / Whether the alarm is a date alarm rather than a weekday alarm
+  pub date_alarm: bool,
+  /// USEL / Whether the Periodic Time Update is every minute rather than every second
+  pub minute_update: bool,
+  /// EERD / Whether the automatic EEPROM refresh is disabled
+  pub eeprom_refresh_disabled: bool,
+  /// TE / Whether the Periodic Countdown Timer is enabled
+  pub timer_enabled: bool,
+  /// TD / Periodic Countdown Timer clock frequency
+  pub timer_clock_freq: TimerClockFreq,
+  /// TSE / Whether the Time Stamp function is enabled
+  pub timestamp_enabled: bool,
+  /// CLKIE / Whether the clock output is enabled by interrupts
+  pub clockout_int_enabled: bool,
+  /// UIE / Periodic Time Update Interrupt enable
+  pub time_update_int_enabled: bool,
+  /// TIE / Periodic Countdown Timer Interrupt enable
+  pub timer_int_enabled: bool,
+  /// AIE / Alarm Interrupt enable
+  pub alarm_int_enabled: bool,
+  /// EIE / External Event Interrupt enable
+  pub event_int_enabled: bool,
+  /// 12_24 / Whether the RTC is in 12 hour mode
+  pub twelve_hour: bool,
+}
+
+impl ControlConfig {
+  // Decode the Control 1 and Control 2 register values
+  fn from_registers(control1: u8, control2: u8) -> Self {
+    ControlConfig {
+      timer_repeating: 0 != control1 & RegControl1Bits::TimerRepeatBit as u8,
+      date_alarm: 0 != control1 & RegControl1Bits::WadaBit as u8,
+      minute_update: 0 != control1 & RegControl1Bits::UselBit as u8,
+      eeprom_refresh_disabled: 0 != control1 & RegControl1Bits::EepromRefreshDisableBit as u8,
+      timer_enabled: 0 != control1 & RegControl1Bits::TimerEnableBit as u8,
+      timer_clock_freq: TimerClockFreq::from_register(control1),
+      timestamp_enabled: 0 != control2 & RegControl2Bits::TimeStampEnableBit as u8,
+      clockout_int_enabled: 0 != control2 & RegControl2Bits::ClockoutIntEnableBit as u8,
+      time_update_int_enabled: 0 != control2 & RegControl2Bits::TimeUpdateIntEnableBit as u8,
+      timer_int_enabled: 0 != control2 & RegControl2Bits::TimerIntEnableBit as u8,
+      alarm_int_enabled: 0 != control2 & RegControl2Bits::AlarmIntEnableBit as u8,
+      event_int_enabled: 0 != control2 & RegControl2Bits::EventIntEnableBit as u8,
+      twelve_hour: 0 != control2 & RegControl2Bits::TwelveHourModeBit as u8,
+    }
+  }
+}
+
+/// Snapshot of the decoded state of the RTC, see `RV3028::read_full_state`
+#[cfg(feature = "chrono")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeviceState {
+  /// Datetime from the BCD registers, or None if they don't hold a valid datetime
+  pub datetime: Option<NaiveDateTime>,
+  /// Whether the RTC is in 12 hour mode
+  pub twelve_hour: bool,
+  /// Alarm settings
+  pub alarm: AlarmConfig,
+  /// Status register flags
+  pub status: StatusFlags,
+  /// Whether the Periodic Countdown Timer is enabled (TE)
+  pub timer_enabled: bool,
+  /// Whether the Periodic Countdown Timer repeats (TRPT)
+  pub timer_repeating: bool,
+  /// Periodic Countdown Timer clock frequency (TD)
+  pub timer_clock_freq: TimerClockFreq,
+  /// Periodic Countdown Timer value (the reload value, in ticks)
+  pub timer_value: u16,
+  /// Whether the CLKOUT pin is enabled (CLKOE)
+  pub clockout_enabled: bool,
+  /// Whether the clock output is controlled by interrupts (CLKIE)
+  pub clockout_interrupt_controlled: bool,
+  /// Clock output frequency (FD)
+  pub clockout_rate: ClockoutRate,
+  /// Whether backup switchover is enabled (BSM)
+  pub backup_switchover_enabled: bool,
+  /// Whether trickle charging is enabled (TCE)
+  pub trickle_charge_enabled: bool,
+  /// Trickle charge current limiter (TCR)
+  pub trickle_charge_limiter: TrickleChargeCurrentLimiter,
+}
 
 /// RV-3028-C7
 /// Extreme Low Power Real-Time Clock (RTC) Module with I2C-Bus Interface
@@ -248,6 +701,13 @@ pub struct RV3028<I2C> {
   i2c: I2C,
   mux_addr: u8,
   mux_chan: u8,
+  #[cfg(feature = "chrono")]
+  weekday_base: Weekday,
+  eeprom_poll_limit: u32,
+  last_countdown_value: Option<u16>,
+  saved_outputs: Option<[u8; LOW_POWER_OUTPUT_BITS.len()]>,
+  #[cfg(feature = "metrics")]
+  transaction_count: u32,
 }
 
 impl<I2C, E> RV3028<I2C>
@@ -261,7 +721,14 @@ impl<I2C, E> RV3028<I2C>
     RV3028 {
       i2c,
       mux_addr: 0u8,
-      mux_chan: 0u8
+      mux_chan: 0u8,
+      #[cfg(feature = "chrono")]
+      weekday_base: Weekday::Mon,
+      eeprom_poll_limit: DEFAULT_EEPROM_POLL_LIMIT,
+      last_countdown_value: None,
+      saved_outputs: None,
+      #[cfg(feature = "metrics")]
+      transaction_count: 0,
     }
   }
 
@@ -273,8 +740,63 @@ impl<I2C, E> RV3028<I2C>
     RV3028 {
       i2c,
       mux_addr,
-      mux_chan
+      mux_chan,
+      #[cfg(feature = "chrono")]
+      weekday_base: Weekday::Mon,
+      eeprom_poll_limit: DEFAULT_EEPROM_POLL_LIMIT,
+      last_countdown_value: None,
+      saved_outputs: None,
+      #[cfg(feature = "metrics")]
+      transaction_count: 0,
+    }
+  }
+
+  /// The 7-bit i2c address the driver uses to talk to the RTC.
+  /// This is fixed by the RV-3028 hardware: multiple RTCs on one bus
+  /// are distinguished by their mux channel instead, see `new_with_mux`.
+  pub fn address(&self) -> u8 {
+    RV3028_ADDRESS
+  }
+
+  /// Set the weekday that the RTC's Weekday counter (and weekday alarm) treats as 0.
+  /// The RTC's weekday counter is a user-assigned 3 bit counter that runs 0..6,
+  /// so the mapping to actual weekdays is a matter of convention:
+  /// - `Weekday::Mon` (the default): Monday = 0 .. Sunday = 6
+  /// - `Weekday::Sun`: Sunday = 0 .. Saturday = 6
+  ///
+  /// This affects `set_datetime` and the weekday alarm methods,
+  /// so it should be set consistently before using those.
+  #[cfg(feature = "chrono")]
+  pub fn set_weekday_convention(&mut self, base: Weekday) {
+    self.weekday_base = base;
+  }
+
+  /// Set the maximum number of times EEPROM operations poll the EEBUSY bit
+  /// while waiting for the EEPROM, before failing with `Error::Timeout`.
+  /// Each poll is one i2c register read, so slow buses need fewer polls
+  /// to cover the same time: raise this on fast buses, or lower it to fail faster.
+  /// Defaults to `DEFAULT_EEPROM_POLL_LIMIT`.
+  pub fn set_eeprom_poll_limit(&mut self, limit: u32) {
+    self.eeprom_poll_limit = limit;
+  }
+
+  /// Convert a weekday to a Weekday register value (0..6),
+  /// where the weekday `base` is counted as 0
+  #[cfg(feature = "chrono")]
+  pub fn weekday_to_register(wd: Weekday, base: Weekday) -> u8 {
+    ((wd.num_days_from_monday() + 7 - base.num_days_from_monday()) % 7) as u8
+  }
+
+  /// Convert a Weekday register value (0..6) to a weekday,
+  /// where the weekday `base` is counted as 0.
+  /// Returns None if the value isn't a valid Weekday register value.
+  #[cfg(feature = "chrono")]
+  pub fn register_to_weekday(val: u8, base: Weekday) -> Option<Weekday> {
+    if val > 6 {
+      return None;
     }
+    let days_from_monday = (base.num_days_from_monday() as u8 + val) % 7;
+    Weekday::try_from(days_from_monday).ok()
   }
 
   // Converts a binary value to BCD format
@@ -287,9 +809,15 @@ impl<I2C, E> RV3028<I2C>
     ((value & 0xF0) >> 4) * 10 + (value & 0x0F)
   }
 
+  // Whether both digits of a BCD value are in the range 0..9
+  fn is_valid_bcd(value: u8) -> bool {
+    (value >> 4) < 10 && (value & 0x0F) < 10
+  }
+
   // If using an i2c mux, tell the mux to select our channel
   fn select_mux_channel(&mut self) -> Result<(), E> {
     if self.mux_addr != 0u8 {
+      self.count_transaction();
       self.i2c.write(self.mux_addr, &[self.mux_chan])
     }
     else {
@@ -297,12 +825,37 @@ impl<I2C, E> RV3028<I2C>
     }
   }
 
+  // Tally one i2c transaction, if the `metrics` feature is enabled
+  #[inline]
+  fn count_transaction(&mut self) {
+    #[cfg(feature = "metrics")]
+    {
+      self.transaction_count = self.transaction_count.wrapping_add(1);
+    }
+  }
+
+  /// Number of i2c transactions (writes or write-reads, including
+  /// mux channel selection) issued by this driver since creation or the last
+  /// `reset_transaction_count`. Wraps around on overflow.
+  /// For example, `set_datetime` costs four transactions without a mux.
+  #[cfg(feature = "metrics")]
+  pub fn transaction_count(&self) -> u32 {
+    self.transaction_count
+  }
+
+  /// Reset the i2c transaction count to zero, see `transaction_count`
+  #[cfg(feature = "metrics")]
+  pub fn reset_transaction_count(&mut self) {
+    self.transaction_count = 0;
+  }
+
   // fn write_register(&mut self, reg: u8, data: u8) -> Result<(), E> {
   //   self.select_mux_channel()?;
   //   self.write_register_raw(reg, data)
   // }
 
   fn write_register_raw(&mut self, reg: u8, data: u8) -> Result<(), E> {
+    self.count_transaction();
     self.i2c.write(RV3028_ADDRESS, &[reg, data])
   }
 
@@ -313,6 +866,7 @@ impl<I2C, E> RV3028<I2C>
 
   fn read_register_raw(&mut self, reg: u8) -> Result<u8, E> {
     let mut buf = [0];
+    self.count_transaction();
     self.i2c.write_read(RV3028_ADDRESS, &[reg], &mut buf)?;
     Ok(buf[0])
   }
@@ -331,6 +885,22 @@ impl<I2C, E> RV3028<I2C>
     Ok(flag_set)
   }
 
+  /// Check whether the RTC's clock is advancing, by reading the Seconds register
+  /// before and after calling `sample_gap_check`.
+  /// - `sample_gap_check` should delay for more than one second: since the driver
+  ///   can't sleep on its own, the caller provides the delay.
+  ///
+  /// A false result means the oscillator may be halted
+  /// (for example, due to a dead backup supply).
+  pub fn is_clock_running(&mut self, mut sample_gap_check: impl FnMut()) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let first_seconds = self.read_register_raw(REG_SECONDS)?;
+    sample_gap_check();
+    self.select_mux_channel()?;
+    let second_seconds = self.read_register_raw(REG_SECONDS)?;
+    Ok(first_seconds != second_seconds)
+  }
+
   /// Check whether an external event has been detected
   /// (an appropriate input signal on the EVI pin)
   pub fn check_and_clear_ext_event(&mut self)-> Result<bool, E>  {
@@ -339,43 +909,178 @@ impl<I2C, E> RV3028<I2C>
     Ok(flag_set)
   }
 
-  // TODO these methods have not been thoroughly tested, and are believed broken.
-  // fn is_eeprom_busy(&mut self) -> Result<bool, E> {
-  //   let status = self.read_register(REG_STATUS)?;
-  //   Ok(status & EEBUSY_BIT != 0)
-  // }
-  //
-  // fn disable_auto_eeprom_refresh(&mut self) -> Result<(), E> {
-  //   let mut control_1 = self.read_register(REG_CONTROL1)?;
-  //   control_1 |= EERD_BIT; // Set EERD bit
-  //   self.write_register(REG_CONTROL1, control_1)
-  // }
-  //
-  // fn enable_auto_eeprom_refresh(&mut self) -> Result<(), E> {
-  //   let mut control_1 = self.read_register(REG_CONTROL1)?;
-  //   control_1 &= !(EERD_BIT); // Clear EERD bit
-  //   self.write_register(REG_CONTROL1, control_1)
-  // }
-  //
-  // pub fn eeprom_read(&mut self, address: u8) -> Result<u8, E> {
-  //   self.disable_auto_eeprom_refresh()?;
-  //   while self.is_eeprom_busy()? {}
-  //   // Read from EEPROM
-  //   self.write_register(EEPROM_ADDRESS, address)?;
-  //   let res = self.read_register(EEPROM_ADDRESS);
-  //   self.enable_auto_eeprom_refresh()?;
-  //   res
-  // }
-  //
-  // pub fn eeprom_write(&mut self, address: u8, data: u8) -> Result<(), E> {
-  //   self.disable_auto_eeprom_refresh()?;
-  //   while self.is_eeprom_busy()? {}
-  //   self.write_register(EEPROM_ADDRESS, address)?;
-  //   let res = self.write_register(EEPROM_ADDRESS, data);
-  //
-  //   self.enable_auto_eeprom_refresh()?;
-  //   res
-  // }
+  /// Clear the event flag (EVF) without reporting whether it was set,
+  /// re-arming external event detection.
+  /// Intended as a fast acknowledgement when capturing high-rate external events,
+  /// where the event count is read separately.
+  pub fn acknowledge_event(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::EventFlagBit as u8)
+  }
+
+  // Check whether the EEPROM is busy with a read or write, skips the mux
+  fn is_eeprom_busy_raw(&mut self) -> Result<bool, E> {
+    let status = self.read_register_raw(REG_STATUS)?;
+    Ok(0 != status & RegStatusBits::EepromBusyBit as u8)
+  }
+
+  // Wait until the EEPROM is no longer busy, skips the mux.
+  // Gives up after `eeprom_poll_limit` polls.
+  fn wait_eeprom_ready_raw(&mut self) -> Result<(), Error<E>> {
+    for _ in 0..self.eeprom_poll_limit {
+      if !self.is_eeprom_busy_raw()? {
+        return Ok(());
+      }
+    }
+    Err(Error::Timeout)
+  }
+
+  // Check that an address is within the user EEPROM or configuration EEPROM
+  fn check_eeprom_address(address: u8) -> Result<(), Error<E>> {
+    if address <= EEPROM_USER_LAST || (EEPROM_CONFIG_FIRST..=EEPROM_CONFIG_LAST).contains(&address) {
+      Ok(())
+    }
+    else {
+      Err(Error::OutOfRange)
+    }
+  }
+
+  // Issue an EEPROM command, skips the mux
+  fn eeprom_command_raw(&mut self, command: u8) -> Result<(), E> {
+    // Each command must be preceded by writing 0x00 to EECMD
+    self.write_register_raw(REG_EEPROM_COMMAND, 0x00)?;
+    self.write_register_raw(REG_EEPROM_COMMAND, command)
+  }
+
+  // Run an EEPROM operation with the automatic EEPROM refresh disabled, skips the mux.
+  // Sets EERD and waits for any in-progress refresh to finish before running `op`,
+  // then clears EERD again, even if `op` failed.
+  // Every EEPROM access should go through this, to avoid colliding with an automatic refresh.
+  fn with_eeprom_access_raw<T>(&mut self, op: impl FnOnce(&mut Self) -> Result<T, Error<E>>)
+    -> Result<T, Error<E>>
+  {
+    self.set_reg_bits_raw(REG_CONTROL1, RegControl1Bits::EepromRefreshDisableBit as u8)?;
+    let res = self.wait_eeprom_ready_raw().and_then(|_| op(self));
+    let restore = self.clear_reg_bits_raw(
+      REG_CONTROL1, RegControl1Bits::EepromRefreshDisableBit as u8);
+    // report the first error to occur
+    let val = res?;
+    restore?;
+    Ok(val)
+  }
+
+  /// Read one byte directly from EEPROM (rather than from its RAM mirror)
+  /// - `address` is either in user EEPROM (0x00..=0x2A)
+  ///   or configuration EEPROM (0x30..=0x37)
+  pub fn eeprom_read(&mut self, address: u8) -> Result<u8, Error<E>> {
+    Self::check_eeprom_address(address)?;
+    self.select_mux_channel()?;
+    let data = self.with_eeprom_access_raw(|rtc| {
+      rtc.write_register_raw(REG_EEPROM_ADDRESS, address)?;
+      rtc.eeprom_command_raw(EEPROM_CMD_READ_ONE)?;
+      rtc.wait_eeprom_ready_raw()?;
+      Ok(rtc.read_register_raw(REG_EEPROM_DATA)?)
+    })?;
+    Ok(data)
+  }
+
+  /// Write one byte directly to EEPROM.
+  /// EEPROM has limited write endurance, so avoid writing frequently.
+  /// - `address` is either in user EEPROM (0x00..=0x2A)
+  ///   or configuration EEPROM (0x30..=0x37)
+  pub fn eeprom_write(&mut self, address: u8, data: u8) -> Result<(), Error<E>> {
+    Self::check_eeprom_address(address)?;
+    self.select_mux_channel()?;
+    self.with_eeprom_access_raw(|rtc| {
+      rtc.write_register_raw(REG_EEPROM_ADDRESS, address)?;
+      rtc.write_register_raw(REG_EEPROM_DATA, data)?;
+      rtc.eeprom_command_raw(EEPROM_CMD_WRITE_ONE)?;
+      rtc.wait_eeprom_ready_raw()
+    })?;
+    Ok(())
+  }
+
+  /// Write one byte to EEPROM, then read it back to confirm it was programmed.
+  /// Returns `Error::VerifyFailed` if the byte read back differs from `data`.
+  /// Note that this can't verify bytes that don't read back as written,
+  /// such as the EEPROM password bytes.
+  pub fn eeprom_write_verified(&mut self, address: u8, data: u8) -> Result<(), Error<E>> {
+    self.eeprom_write(address, data)?;
+    if data != self.eeprom_read(address)? {
+      return Err(Error::VerifyFailed);
+    }
+    Ok(())
+  }
+
+  /// Write a `u32` counter to a wear-leveled ring of slots in user EEPROM,
+  /// for values that update often, such as a boot counter.
+  /// - `slot_start` user EEPROM address of the first slot
+  /// - `slot_count` number of slots in the ring, at least 1
+  /// - `value` the new counter value
+  ///
+  /// Each slot takes `USER_EEPROM_COUNTER_SLOT_SIZE` (5) bytes: a sequence byte followed by
+  /// the little-endian value, so the ring occupies `slot_count * 5` bytes from `slot_start`,
+  /// and the 43 bytes of user EEPROM hold at most 8 slots.
+  /// Each write goes to the slot after the most recent one, with a sequence byte one greater:
+  /// this spreads the wear over the ring, extending EEPROM lifetime by about `slot_count` times.
+  /// The most recent slot is found by scanning for the break in the sequence.
+  /// The sequence byte is written last, so an interrupted write leaves the previous value intact.
+  /// Writing the value that is already stored doesn't write anything.
+  /// Returns `Error::OutOfRange` if the ring doesn't fit in user EEPROM (0x00..=0x2A).
+  pub fn write_user_eeprom_counter(&mut self, slot_start: u8, slot_count: u8, value: u32)
+    -> Result<(), Error<E>>
+  {
+    let (latest, seq) = self.find_user_eeprom_counter(slot_start, slot_count)?;
+    if value == self.read_user_eeprom_counter_slot(slot_start, latest)? {
+      return Ok(());
+    }
+    let next = (latest + 1) % slot_count;
+    let addr = slot_start + next * USER_EEPROM_COUNTER_SLOT_SIZE;
+    for (i, byte) in value.to_le_bytes().iter().enumerate() {
+      self.eeprom_write(addr + 1 + i as u8, *byte)?;
+    }
+    self.eeprom_write(addr, seq.wrapping_add(1))
+  }
+
+  /// Read the most recent value of a wear-leveled counter in user EEPROM,
+  /// see `write_user_eeprom_counter` for the storage scheme.
+  /// Returns `Error::OutOfRange` if the ring doesn't fit in user EEPROM (0x00..=0x2A).
+  pub fn read_user_eeprom_counter(&mut self, slot_start: u8, slot_count: u8) -> Result<u32, Error<E>> {
+    let (latest, _seq) = self.find_user_eeprom_counter(slot_start, slot_count)?;
+    self.read_user_eeprom_counter_slot(slot_start, latest)
+  }
+
+  // Find the most recently written slot of a user EEPROM counter ring,
+  // returning its index and sequence byte
+  fn find_user_eeprom_counter(&mut self, slot_start: u8, slot_count: u8) -> Result<(u8, u8), Error<E>> {
+    let ring_size = slot_count as usize * USER_EEPROM_COUNTER_SLOT_SIZE as usize;
+    if 0 == slot_count || slot_start as usize + ring_size > EEPROM_USER_LAST as usize + 1 {
+      return Err(Error::OutOfRange);
+    }
+    let mut seqs = [0u8; MAX_USER_EEPROM_COUNTER_SLOTS];
+    for slot in 0..slot_count {
+      seqs[slot as usize] = self.eeprom_read(slot_start + slot * USER_EEPROM_COUNTER_SLOT_SIZE)?;
+    }
+    let latest = Self::latest_counter_slot(&seqs[..slot_count as usize]);
+    Ok((latest as u8, seqs[latest]))
+  }
+
+  // Read the value stored in one slot of a user EEPROM counter ring
+  fn read_user_eeprom_counter_slot(&mut self, slot_start: u8, slot: u8) -> Result<u32, Error<E>> {
+    let addr = slot_start + slot * USER_EEPROM_COUNTER_SLOT_SIZE;
+    let mut bytes = [0u8; 4];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+      *byte = self.eeprom_read(addr + 1 + i as u8)?;
+    }
+    Ok(u32::from_le_bytes(bytes))
+  }
+
+  // The most recent slot is the last one whose successor doesn't continue the sequence
+  fn latest_counter_slot(seqs: &[u8]) -> usize {
+    (0..seqs.len())
+      .find(|i| seqs[(i + 1) % seqs.len()] != seqs[*i].wrapping_add(1))
+      .unwrap_or(0)
+  }
 
   // // set specific bits in a register:
   // // all bits must be high that you wish to set
@@ -436,6 +1141,27 @@ impl<I2C, E> RV3028<I2C>
     Ok(conf_val)
   }
 
+  /// Configure trickle charging as for `toggle_trickle_charge`,
+  /// then read back the full trickle charge configuration as set,
+  /// so the caller can confirm the exact current limiter took effect.
+  /// Returns whether trickle charging is enabled, and the current limiter.
+  pub fn config_trickle_charge(&mut self, enable: bool,
+                               limit_resistance: TrickleChargeCurrentLimiter)
+    -> Result<(bool, TrickleChargeCurrentLimiter), E>
+  {
+    self.toggle_trickle_charge(enable, limit_resistance)?;
+    self.get_trickle_charge_config()
+  }
+
+  /// Read the trickle charge configuration:
+  /// whether trickle charging is enabled (TCE), and the current limiter (TCR)
+  pub fn get_trickle_charge_config(&mut self) -> Result<(bool, TrickleChargeCurrentLimiter), E> {
+    self.select_mux_channel()?;
+    let reg_val = self.read_register_raw(EEPROM_MIRROR_ADDRESS)?;
+    let enabled = 0 != reg_val & RegEepromMirrorBits::TrickleChargeEnableBit as u8;
+    Ok((enabled, TrickleChargeCurrentLimiter::from_register(reg_val)))
+  }
+
   /// Toggle whether the Vbackup power source should be used
   /// when Vdd supply level drops below useful level.
   /// - `enable` enables switching to Vbackup, disables if false
@@ -450,6 +1176,20 @@ impl<I2C, E> RV3028<I2C>
     Ok(conf_val)
   }
 
+  /// Whether the Periodic Countdown Timer keeps running after switching to Vbackup.
+  /// The timer is clocked from the 32.768 kHz oscillator, which keeps running
+  /// in backup mode, so there's no timer-specific setting: the timer (and its
+  /// interrupt on the INT pin) continues as long as backup switchover is enabled,
+  /// ie the BSM bits select direct (01) or level (11) switching mode.
+  /// Otherwise, the whole RTC stops when Vdd is lost.
+  /// See `toggle_backup_switchover` to enable switchover.
+  pub fn timer_persists_on_backup(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let backup = self.read_register_raw(EEPROM_MIRROR_ADDRESS)?;
+    // BSM = 01 or 11: the low BSM bit is set in both switching modes
+    Ok(0 != backup & RegEepromMirrorBits::BackupSwitchoverDsm as u8)
+  }
+
   /// Disable all clock outputs triggered by interrupts
   pub fn clear_all_int_clockout_bits(&mut self) -> Result<(), E> {
     self.select_mux_channel()?;
@@ -461,6 +1201,14 @@ impl<I2C, E> RV3028<I2C>
   }
 
 
+  /// Read the raw EEPROM Password Enable (EEPWE) byte, from its RAM mirror.
+  /// Write protection is enabled only if this is 255: any other value means unprotected.
+  /// The exact value is useful when diagnosing the write protection setup.
+  pub fn get_write_protect_enable_byte(&mut self) -> Result<u8, E> {
+    self.select_mux_channel()?;
+    self.read_register_raw(EEPROM_PW_ENABLE_ADDRESS)
+  }
+
   /// Get the current value of the EEPROM mirror from RAM
   pub fn get_eeprom_mirror_value(&mut self) -> Result<u8, E> {
     self.select_mux_channel()?;
@@ -468,15 +1216,49 @@ impl<I2C, E> RV3028<I2C>
     Ok(reg_val)
   }
 
-  // Set the bcd time tracking registers
+  // Encode a 0..23 hour for the hours registers: if the RTC is in 12 hour mode,
+  // this is a 1..12 hour plus the AMPM bit
+  fn hour_to_register(hour: u8, twelve_hour: bool) -> u8 {
+    if twelve_hour {
+      let pm_flag = if hour >= 12 { HOURS_PM_FLAG } else { 0 };
+      let hour_12 = match hour % 12 { 0 => 12, other => other };
+      pm_flag | Self::bin_to_bcd(hour_12)
+    }
+    else {
+      Self::bin_to_bcd(hour)
+    }
+  }
+
+  // Decode a value from the hours registers into a 0..23 hour
+  fn hour_from_register(raw: u8, twelve_hour: bool) -> u8 {
+    if twelve_hour {
+      let hour_12 = Self::bcd_to_bin(raw & 0x1F) % 12;
+      if 0 != raw & HOURS_PM_FLAG { hour_12 + 12 } else { hour_12 }
+    }
+    else {
+      Self::bcd_to_bin(raw & 0x3F)
+    }
+  }
+
+  // Check whether the 12_24 bit has put the RTC in 12 hour mode, skips the mux
+  fn is_twelve_hour_mode_raw(&mut self) -> Result<bool, E> {
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    Ok(0 != control2 & RegControl2Bits::TwelveHourModeBit as u8)
+  }
+
+  // Set the bcd time tracking registers, using the hour encoding
+  // that matches the RTC's current 12 or 24 hour mode.
   // assumes `select_mux_channel` has already been called
+  #[cfg(feature = "chrono")]
   fn set_time_raw(&mut self, time: &NaiveTime) -> Result<(), E> {
+    let twelve_hour = self.is_twelve_hour_mode_raw()?;
     let write_buf = [
       REG_SECONDS, // select the first register
       Self::bin_to_bcd(time.second() as u8 ),
       Self::bin_to_bcd(time.minute() as u8 ),
-      Self::bin_to_bcd(time.hour() as u8 )
+      Self::hour_to_register(time.hour() as u8, twelve_hour)
     ];
+    self.count_transaction();
     self.i2c.write(RV3028_ADDRESS, &write_buf)
   }
 
@@ -484,11 +1266,12 @@ impl<I2C, E> RV3028<I2C>
   // Set the internal BCD date registers.
   // Note that only years from 2000 to 2099 are supported.
   // Assumes `select_mux_channel` has already been called
+  #[cfg(feature = "chrono")]
   fn set_date_raw(&mut self, date: &NaiveDate) -> Result<(), E> {
     let year = if date.year() > 2000 { (date.year() - 2000) as u8} else {0};
     let month = (date.month() % 13) as u8;
     let day = (date.day() % 32) as u8;
-    let weekday = (date.weekday() as u8) % 7;
+    let weekday = Self::weekday_to_register(date.weekday(), self.weekday_base);
 
     let write_buf = [
       REG_WEEKDAY, // select the first register
@@ -497,6 +1280,7 @@ impl<I2C, E> RV3028<I2C>
       Self::bin_to_bcd(month ),
       Self::bin_to_bcd(year )
     ];
+    self.count_transaction();
     self.i2c.write(RV3028_ADDRESS, &write_buf)
   }
 
@@ -515,12 +1299,182 @@ impl<I2C, E> RV3028<I2C>
   pub fn get_hms(&mut self) -> Result<(u8, u8, u8), E> {
     let mut read_buf = [0u8;3];
     self.read_multi_registers(REG_SECONDS, &mut read_buf)?;
-    let seconds = Self::bcd_to_bin(read_buf[0]);
-    let minutes = Self::bcd_to_bin(read_buf[1]);
-    let hours = Self::bcd_to_bin(read_buf[2]);
+    // mask out reserved bits, in case they read as 1
+    let seconds = Self::bcd_to_bin(read_buf[0] & 0x7F);
+    let minutes = Self::bcd_to_bin(read_buf[1] & 0x7F);
+    let hours = Self::bcd_to_bin(read_buf[2] & 0x3F);
     Ok( (hours, minutes, seconds) )
   }
 
+  /// Read the raw BCD Seconds, Minutes, Hours registers into `buf`, in that order,
+  /// without any conversion: useful for compact high-rate logging, decoded later.
+  /// Note the Hours register encoding depends on 12 or 24 hour mode.
+  pub fn get_time_bcd_raw(&mut self, buf: &mut [u8; 3]) -> Result<(), E> {
+    self.read_multi_registers(REG_SECONDS, buf)
+  }
+
+  /// Align the RTC's second boundary with the moment this is called,
+  /// eg on a reference 1 PPS edge, by rewriting the Seconds register,
+  /// which resets the prescaler (zeroing subseconds).
+  /// The next second tick (and rising edge of a 1 Hz CLKOUT) then occurs one second later.
+  /// The fraction of a second elapsed since the last tick is discarded,
+  /// and alignment precision is bounded by the latency of the three i2c reads
+  /// and one write leading up to the Seconds write.
+  ///
+  /// If the RTC ticks between reading and rewriting the Seconds register,
+  /// writing back the stale value would lose that second. The Unix time counter,
+  /// which ticks together with the Seconds register, is read around both steps to detect this:
+  /// the Seconds value is re-read if it may be stale, or incremented after the write
+  /// if the tick landed just before it. Minutes and above have already been carried by the RTC.
+  ///
+  /// Note that unlike some other RTCs, the RV-3028 has no STOP bit to freeze the
+  /// clock while loading new values. Instead, the time setting methods such as
+  /// `set_datetime` write the date registers, then the time registers starting with Seconds:
+  /// that write resets the prescaler, so the new time starts counting from a whole second
+  /// boundary at that moment. `set_datetime` then writes the Unix counter last,
+  /// well before the next tick, which doesn't reset the prescaler.
+  /// To set the time precisely, issue that call (or this one) right at the reference edge.
+  pub fn align_to_external_pps(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    let unix_before = self.get_unix_time_raw()?;
+    let mut seconds = self.read_register_raw(REG_SECONDS)? & 0x7F;
+    let unix_read = self.get_unix_time_raw()?;
+    if unix_read != unix_before {
+      // Ticked around the Seconds read: the next tick is a second away, so re-reading is safe
+      seconds = self.read_register_raw(REG_SECONDS)? & 0x7F;
+    }
+    self.write_register_raw(REG_SECONDS, seconds)?;
+    // The write reset the prescaler, so the counter can't have ticked since
+    let unix_written = self.get_unix_time_raw()?;
+    if unix_written != unix_read {
+      // Ticked after the Seconds read but before the write, which discarded that tick
+      let next = (Self::bcd_to_bin(seconds) + 1) % 60;
+      self.write_register_raw(REG_SECONDS, Self::bin_to_bcd(next))?;
+    }
+    Ok(())
+  }
+
+  /// Read the raw BCD Weekday, Date, Month, Year registers into `buf`, in that order,
+  /// without any conversion: useful for compact logging, or for diagnosing
+  /// the year (offset from 2000) and weekday encodings.
+  pub fn get_date_bcd_raw(&mut self, buf: &mut [u8; 4]) -> Result<(), E> {
+    self.read_multi_registers(REG_WEEKDAY, buf)
+  }
+
+  /// Ensure the RTC is in 24 hour mode, as at startup when taking over an RTC
+  /// whose hour mode is unknown.
+  /// If the RTC is in 12 hour mode, this switches to 24 hour mode and rewrites
+  /// the Hours and Hours Alarm registers in the 24 hour encoding,
+  /// preserving the current time and the alarm hour (and its match enable bit).
+  pub fn normalize_hour_mode(&mut self) -> Result<(), E> {
+    self.set_hour_mode_preserving_time(false)
+  }
+
+  /// Switch between 12 and 24 hour mode (the 12_24 bit) without changing the time.
+  /// Switching the bit alone changes how the Hours and Hours Alarm registers are interpreted,
+  /// so this also rewrites both in the new encoding, preserving the current time
+  /// and the alarm hour (and its match enable bit).
+  /// Does nothing if the RTC is already in the requested mode.
+  /// Avoid calling this just before the hour rolls over, as the Hours register
+  /// is read and then rewritten.
+  pub fn set_hour_mode_preserving_time(&mut self, twelve_hour: bool) -> Result<(), E> {
+    self.select_mux_channel()?;
+    let mode_bit = RegControl2Bits::TwelveHourModeBit as u8;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let was_twelve_hour = 0 != control2 & mode_bit;
+    if was_twelve_hour == twelve_hour {
+      return Ok(());
+    }
+    let hours_raw = self.read_register_raw(REG_HOURS)?;
+    let alarm_hours_raw = self.read_register_raw(REG_HOURS_ALARM)?;
+    self.write_register_raw(REG_CONTROL2,
+                            if twelve_hour { control2 | mode_bit } else { control2 & !mode_bit })?;
+    let hours = Self::hour_from_register(hours_raw, was_twelve_hour);
+    self.write_register_raw(REG_HOURS, Self::hour_to_register(hours, twelve_hour))?;
+    let alarm_hours = Self::hour_from_register(alarm_hours_raw, was_twelve_hour);
+    self.write_register_raw(REG_HOURS_ALARM,
+                            (alarm_hours_raw & ALARM_NO_WATCH_FLAG) | Self::hour_to_register(alarm_hours, twelve_hour))
+  }
+
+  /// Estimate how far (in microseconds) the RTC was into the current second
+  /// when this method was called, for use in clock disciplining.
+  /// The RTC doesn't expose subseconds, so this polls the Seconds register
+  /// (up to `samples` times) until it observes two consecutive second ticks,
+  /// and uses the number of polls per second to scale the position of the first tick.
+  /// - This blocks for between one and two seconds of continuous i2c reads.
+  /// - The result is only an estimate, bounded by the i2c read latency.
+  ///
+  /// Returns `Error::Timeout` if `samples` was too small to observe two ticks.
+  pub fn estimate_second_phase(&mut self, samples: u32) -> Result<u32, Error<E>> {
+    self.select_mux_channel()?;
+    let mut last_seconds = self.read_register_raw(REG_SECONDS)?;
+    let mut first_tick: Option<u32> = None;
+    for i in 1..samples {
+      let seconds = self.read_register_raw(REG_SECONDS)?;
+      if seconds != last_seconds {
+        last_seconds = seconds;
+        if let Some(first) = first_tick {
+          // samples taken per second, from the spacing between two ticks
+          let samples_per_sec = (i - first) as u64;
+          let elapsed_micros = (first as u64) * 1_000_000 / samples_per_sec;
+          let phase = 1_000_000u64.saturating_sub(elapsed_micros) % 1_000_000;
+          return Ok(phase as u32);
+        }
+        first_tick = Some(i);
+      }
+    }
+    Err(Error::Timeout)
+  }
+
+  // Read the BCD timekeeping registers (Seconds through Year) as a datetime.
+  // If the Seconds register changes while reading, the registers may have rolled over
+  // mid-read (eg 23:59:59 to 00:00:00), so the whole read is retried,
+  // up to MAX_CONSISTENT_READS times.
+  #[cfg(feature = "chrono")]
+  fn read_bcd_datetime(&mut self) -> Result<NaiveDateTime, Error<E>> {
+    self.select_mux_channel()?;
+    let twelve_hour = self.is_twelve_hour_mode_raw()?;
+    let mut read_buf = [0u8; 7];
+    let mut consistent = false;
+    for _ in 0..MAX_CONSISTENT_READS {
+      self.read_multi_registers_raw(REG_SECONDS, &mut read_buf)?;
+      let seconds = self.read_register_raw(REG_SECONDS)?;
+      if seconds == read_buf[0] {
+        consistent = true;
+        break;
+      }
+    }
+    if !consistent {
+      return Err(Error::Timeout);
+    }
+    Self::datetime_from_registers(&read_buf, twelve_hour).ok_or(Error::InvalidDateTime)
+  }
+
+  /// Read the current datetime from the BCD calendar registers
+  /// (rather than from the Unix time counter, as `datetime` does).
+  /// The read is retried if the seconds roll over while reading,
+  /// so the returned datetime is always consistent.
+  /// Returns `Error::InvalidDateTime` if the registers don't hold a valid datetime,
+  /// or `Error::Timeout` if the seconds never hold still across a read.
+  #[cfg(feature = "chrono")]
+  pub fn get_datetime_atomic(&mut self) -> Result<NaiveDateTime, Error<E>> {
+    self.read_bcd_datetime()
+  }
+
+  // Assemble a datetime from the contents of the registers Seconds through Year.
+  // The Weekday register is ignored.
+  #[cfg(feature = "chrono")]
+  fn datetime_from_registers(regs: &[u8; 7], twelve_hour: bool) -> Option<NaiveDateTime> {
+    let seconds = Self::bcd_to_bin(regs[0] & 0x7F);
+    let minutes = Self::bcd_to_bin(regs[1] & 0x7F);
+    let hours = Self::hour_from_register(regs[2], twelve_hour);
+    let day = Self::bcd_to_bin(regs[4] & 0x3F);
+    let month = Self::bcd_to_bin(regs[5] & 0x1F);
+    let year: i32 = Self::bcd_to_bin(regs[6]) as i32 + 2000;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)?
+      .and_hms_opt(hours as u32, minutes as u32, seconds as u32)
+  }
+
   // read a block of registers all at once
   fn read_multi_registers(&mut self, reg: u8, read_buf: &mut [u8] )  -> Result<(), E> {
     self.select_mux_channel()?;
@@ -529,9 +1483,30 @@ impl<I2C, E> RV3028<I2C>
 
   // read a block of registers all at once: skip mux
   fn read_multi_registers_raw(&mut self, reg: u8, read_buf: &mut [u8] )  -> Result<(), E> {
+    self.count_transaction();
     self.i2c.write_read(RV3028_ADDRESS, &[reg], read_buf)
   }
 
+  /// Set the date and time from separate date and time-of-day values.
+  /// Like `set_datetime`, this sets the Unix time counter and all the BCD
+  /// date and time registers, and resets the prescaler when writing seconds.
+  #[cfg(feature = "chrono")]
+  pub fn set_date_and_time(&mut self, date: &NaiveDate, time: &NaiveTime) -> Result<(), Error<E>> {
+    self.set_datetime(&date.and_time(*time))
+  }
+
+  /// Set only the BCD date and time registers, leaving the Unix time counter untouched.
+  /// This suits applications that only use the calendar registers and the
+  /// alarm / event features, which don't use the Unix time counter.
+  /// The date registers are written first and the time registers last,
+  /// so writing the Seconds register resets the prescaler (zeroing subseconds).
+  #[cfg(feature = "chrono")]
+  pub fn set_bcd_datetime_only(&mut self, datetime: &NaiveDateTime) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.set_date_raw(&datetime.date())?;
+    self.set_time_raw(&datetime.time())
+  }
+
   /// Set just the Unix time counter.
   /// Prefer the `set_datetime` method to properly set all internal BCD registers.
   /// Note:
@@ -546,12 +1521,85 @@ impl<I2C, E> RV3028<I2C>
     self.set_unix_time_raw(unix_time)
   }
 
+  /// Set the Unix time counter from a UTC datetime, truncated to whole seconds.
+  /// As for `set_unix_time`, this doesn't set the BCD date and time registers.
+  /// Returns `Error::OutOfRange` if the datetime is before 1970
+  /// or beyond the Unix time counter (early 2106).
+  #[cfg(feature = "chrono")]
+  pub fn set_unix_time_from_datetime(&mut self, dt: &chrono::DateTime<chrono::Utc>) -> Result<(), Error<E>> {
+    let unix_time: u32 = dt.timestamp().try_into().map_err(|_| Error::OutOfRange)?;
+    self.set_unix_time(unix_time)?;
+    Ok(())
+  }
+
   // sets the unix time counter but skips the mux
   fn set_unix_time_raw(&mut self, unix_time: u32) -> Result<(), E> {
     let bytes = unix_time.to_le_bytes(); // Convert to little-endian byte array
+    self.count_transaction();
     self.i2c.write(RV3028_ADDRESS, &[REG_UNIX_TIME_0, bytes[0], bytes[1], bytes[2], bytes[3]])
   }
 
+  /// Measure the average latency of setting the Unix time counter, for calibrating
+  /// clock disciplining code.
+  /// - `iterations` number of `set_unix_time` calls to time
+  /// - `clock` caller-provided monotonic clock, in any units (eg nanoseconds)
+  ///
+  /// Each iteration reads the current Unix time and writes it back, timing only the write.
+  /// A second that ticks between a read and its write-back is lost from the counter,
+  /// so afterwards the counter is checked against the BCD time of day
+  /// (which the writes don't disturb) and any lost seconds are restored.
+  /// This assumes the whole measurement takes less than a day.
+  /// Returns the average latency in the units of `clock`, or zero if `iterations` is zero.
+  /// Returns `Error::Timeout` if the counter couldn't be read consistently
+  /// with the time of day, or the restored value didn't stick.
+  pub fn measure_write_latency(&mut self, iterations: u32, clock: impl Fn() -> u64) -> Result<u64, Error<E>> {
+    if 0 == iterations {
+      return Ok(0);
+    }
+    self.select_mux_channel()?;
+    let twelve_hour = self.is_twelve_hour_mode_raw()?;
+    let (unix_start, day_secs_start) = self.read_unix_and_day_seconds_raw(twelve_hour)?;
+    let mut total: u64 = 0;
+    for _ in 0..iterations {
+      let unix_time = self.get_unix_time_raw()?;
+      let start = clock();
+      self.set_unix_time_raw(unix_time)?;
+      total = total.saturating_add(clock().saturating_sub(start));
+    }
+
+    // Restore any seconds lost to a tick between a read and its write-back,
+    // checking again in case a tick also lands on the restoring write
+    for _ in 0..MAX_CONSISTENT_READS {
+      let (unix_now, day_secs_now) = self.read_unix_and_day_seconds_raw(twelve_hour)?;
+      let elapsed = (day_secs_now + SECONDS_PER_DAY - day_secs_start) % SECONDS_PER_DAY;
+      let expected = unix_start.wrapping_add(elapsed);
+      if unix_now == expected {
+        return Ok(total / iterations as u64);
+      }
+      self.set_unix_time_raw(expected)?;
+    }
+    Err(Error::Timeout)
+  }
+
+  // Read the Unix time counter along with the BCD time of day, in seconds since midnight,
+  // retrying until the Seconds register is the same before and after the counter is read,
+  // so that both values come from the same second. Skips the mux.
+  fn read_unix_and_day_seconds_raw(&mut self, twelve_hour: bool) -> Result<(u32, u32), Error<E>> {
+    for _ in 0..MAX_CONSISTENT_READS {
+      let mut time_buf = [0u8; 3];
+      self.read_multi_registers_raw(REG_SECONDS, &mut time_buf)?;
+      let unix_time = self.get_unix_time_raw()?;
+      let seconds = self.read_register_raw(REG_SECONDS)?;
+      if seconds == time_buf[0] {
+        let day_secs = Self::hour_from_register(time_buf[2], twelve_hour) as u32 * 3600
+          + Self::bcd_to_bin(time_buf[1] & 0x7F) as u32 * 60
+          + Self::bcd_to_bin(time_buf[0] & 0x7F) as u32;
+        return Ok((unix_time, day_secs));
+      }
+    }
+    Err(Error::Timeout)
+  }
+
   /// Reads the value of the RTC's unix time counter, notionally seconds elapsed since the
   /// common "unix epoch" in the year 1970. It cannot represent datetimes from prior to 1970.
   /// - Note that this is an unsigned u32 value, with different characteristics from the
@@ -561,8 +1609,14 @@ impl<I2C, E> RV3028<I2C>
   /// - Note that the RTC's automatic leap year correction is only valid until 2099
   /// See the App Manual section "3.10. UNIX TIME REGISTERS"
   pub fn get_unix_time(&mut self) -> Result<u32, E> {
+    self.select_mux_channel()?;
+    self.get_unix_time_raw()
+  }
+
+  // Read the Unix time counter, skips the mux
+  fn get_unix_time_raw(&mut self) -> Result<u32, E> {
     let mut read_buf = [0u8; 4];
-    self.read_multi_registers(REG_UNIX_TIME_0, &mut read_buf)?;
+    self.read_multi_registers_raw(REG_UNIX_TIME_0, &mut read_buf)?;
     let val = u32::from_le_bytes(read_buf);
     Ok(val)
   }
@@ -571,25 +1625,334 @@ impl<I2C, E> RV3028<I2C>
   /// in case an internal increment or timestamp set is interspersed between the multi-byte read.
   /// This method performs the recommended read-twice.
   pub fn get_unix_time_blocking(&mut self) -> Result<u32, E> {
+    Ok(self.get_unix_time_with_retries()?.0)
+  }
+
+  /// Read the Unix time counter as for `get_unix_time_blocking`,
+  /// also returning how many times the read-twice had to be retried
+  /// because the two reads disagreed.
+  /// Returns the stable Unix time and the retry count.
+  /// An occasional retry is expected when the counter increments between reads,
+  /// but a persistently high retry count suggests i2c bus integrity problems.
+  pub fn get_unix_time_with_retries(&mut self) -> Result<(u32, u32), E> {
+    let mut retries: u32 = 0;
     loop {
       let val1 = self.get_unix_time()?;
       let val2 = self.get_unix_time()?;
 
       if val1 == val2 {
-        return Ok(val2)
+        return Ok((val2, retries))
       }
+      retries = retries.saturating_add(1);
     }
   }
 
-  /// Toggle whether EVI events trigger on high/rising or low/falling edges
-  pub fn toggle_event_high_low(&mut self, high: bool) -> Result<(), E> {
-    self.set_or_clear_reg_bits(REG_EVENT_CONTROL, RegEventControlBits::EventHighLowBit as u8, high)
+  /// Read the ID register, returning the Hardware ID (HID, upper nibble)
+  /// and the Version ID (VID, lower nibble) of the silicon.
+  /// The Unix time counter needs reading twice whatever the VID,
+  /// see `UNIX_READ_WORKAROUND_REQUIRED`.
+  pub fn get_device_id(&mut self) -> Result<(u8, u8), E> {
+    self.select_mux_channel()?;
+    let id = self.read_register_raw(REG_ID)?;
+    Ok((id >> 4, id & 0x0F))
   }
 
-  /// Enable INT pin output when alarm occurs
-  pub fn toggle_alarm_int_enable(&mut self, enable: bool) -> Result<(), E> {
-    self.set_or_clear_reg_bits(REG_CONTROL2, RegControl2Bits::AlarmIntEnableBit as u8, enable)
-  }
+  /// Startup sanity check of the i2c link to the RTC:
+  /// - reads the ID register, which must not read as all zeros or all ones
+  ///   (as from a missing device or a floating bus)
+  /// - writes test patterns to the two bytes of User RAM and reads them back,
+  ///   then restores the original User RAM contents
+  /// - reads the EEPROM busy bit (EEBUSY), waiting for it to clear as for an EEPROM access
+  ///
+  /// Returns `Error::VerifyFailed` if the ID or the User RAM read back are implausible,
+  /// which also happens if write protection is locking the RTC registers,
+  /// or `Error::Timeout` if the EEPROM stays busy.
+  pub fn self_test(&mut self) -> Result<(), Error<E>> {
+    let (hid, vid) = self.get_device_id()?;
+    let id = (hid << 4) | vid;
+    if 0x00 == id || 0xFF == id {
+      return Err(Error::VerifyFailed);
+    }
+
+    let original = self.get_user_ram()?;
+    for pattern in [[0x55, 0xAA], [0xAA, 0x55]] {
+      if !self.set_user_ram_verified(&pattern)? {
+        // best effort restore: report the verification failure rather than any restore error
+        let _ = self.set_user_ram(&original);
+        return Err(Error::VerifyFailed);
+      }
+    }
+    self.set_user_ram(&original)?;
+
+    self.select_mux_channel()?;
+    self.wait_eeprom_ready_raw()
+  }
+
+  /// Read the current time from both the Unix time counter (read twice, as recommended)
+  /// and the BCD calendar registers, for example to compare or log them side by side.
+  /// Returns the Unix time and the BCD datetime.
+  /// Returns `Error::InvalidDateTime` if the BCD registers don't hold a valid datetime.
+  #[cfg(feature = "chrono")]
+  pub fn get_both_times(&mut self) -> Result<(u32, NaiveDateTime), Error<E>> {
+    let unix_time = self.get_unix_time_blocking()?;
+    let bcd_datetime = self.read_bcd_datetime()?;
+    Ok((unix_time, bcd_datetime))
+  }
+
+  /// Read the BCD datetime and then the Unix time counter back-to-back,
+  /// for diagnosing drift between the two: the registers aren't contiguous,
+  /// so this can't be a single transaction, but the two reads follow each other directly.
+  /// Like `get_both_times`, the Unix time counter is read twice, as recommended.
+  /// Returns the BCD datetime and the Unix time.
+  /// Returns `Error::InvalidDateTime` if the BCD registers don't hold a valid datetime.
+  #[cfg(feature = "chrono")]
+  pub fn read_time_and_unix(&mut self) -> Result<(NaiveDateTime, u32), Error<E>> {
+    let bcd_datetime = self.read_bcd_datetime()?;
+    let unix_time = self.get_unix_time_blocking()?;
+    Ok((bcd_datetime, unix_time))
+  }
+
+  /// Read a decoded snapshot of the RTC's time, alarm, timer, status, and configuration,
+  /// using two block reads: the registers Seconds through Control 2,
+  /// and the RAM mirror of the configuration EEPROM (Clkout through Backup).
+  #[cfg(feature = "chrono")]
+  pub fn read_full_state(&mut self) -> Result<DeviceState, E> {
+    let mut regs = [0u8; (REG_CONTROL2 + 1) as usize];
+    self.read_multi_registers(REG_SECONDS, &mut regs)?;
+    let mut config = [0u8; (EEPROM_MIRROR_ADDRESS - EEPROM_CLKOUT_ADDRESS + 1) as usize];
+    self.read_multi_registers_raw(EEPROM_CLKOUT_ADDRESS, &mut config)?;
+
+    let control1 = regs[REG_CONTROL1 as usize];
+    let control2 = regs[REG_CONTROL2 as usize];
+    let clkout = config[0];
+    let backup = config[(EEPROM_MIRROR_ADDRESS - EEPROM_CLKOUT_ADDRESS) as usize];
+    let twelve_hour = 0 != control2 & RegControl2Bits::TwelveHourModeBit as u8;
+
+    // Seconds through Year
+    let mut time_regs = [0u8; 7];
+    time_regs.copy_from_slice(&regs[REG_SECONDS as usize..REG_SECONDS as usize + 7]);
+    let mut alarm_regs = [0u8; 3];
+    alarm_regs.copy_from_slice(
+      &regs[REG_MINUTES_ALARM as usize..(REG_WEEKDAY_DATE_ALARM + 1) as usize]);
+    let timer_value = u16::from_le_bytes(
+      [regs[REG_TIMER_VALUE0 as usize], regs[REG_TIMER_VALUE0 as usize + 1]]) & Self::MAX_PCT_TICKS;
+
+    Ok(DeviceState {
+      datetime: Self::datetime_from_registers(&time_regs, twelve_hour),
+      twelve_hour,
+      alarm: Self::alarm_config_from_registers(&alarm_regs, control1, control2),
+      status: StatusFlags::from_register(regs[REG_STATUS as usize]),
+      timer_enabled: 0 != control1 & RegControl1Bits::TimerEnableBit as u8,
+      timer_repeating: 0 != control1 & RegControl1Bits::TimerRepeatBit as u8,
+      timer_clock_freq: TimerClockFreq::from_register(control1),
+      timer_value,
+      clockout_enabled: 0 != clkout & RegEepromClkoutBits::ClockoutEnableBit as u8,
+      clockout_interrupt_controlled: 0 != control2 & RegControl2Bits::ClockoutIntEnableBit as u8,
+      clockout_rate: ClockoutRate::from_register(clkout),
+      backup_switchover_enabled: 0 != backup & RegEepromMirrorBits::BackupSwitchoverDsm as u8,
+      trickle_charge_enabled: 0 != backup & RegEepromMirrorBits::TrickleChargeEnableBit as u8,
+      trickle_charge_limiter: TrickleChargeCurrentLimiter::from_register(backup),
+    })
+  }
+
+  /// Read Control 1 and Control 2 together, decoding every setting in them.
+  /// The self-clearing RESET bit always reads as 0, so it isn't included.
+  pub fn get_control_config(&mut self) -> Result<ControlConfig, E> {
+    let mut regs = [0u8; 2];
+    self.read_multi_registers(REG_CONTROL1, &mut regs)?;
+    Ok(ControlConfig::from_registers(regs[0], regs[1]))
+  }
+
+  /// Summarize the configuration that drives the RTC's current draw:
+  /// the clock output, trickle charging, backup switchover, and INT pin interrupts.
+  /// This doesn't estimate the actual current, which also depends on the supply voltage
+  /// and the load on the output pins.
+  pub fn active_power_features(&mut self) -> Result<PowerFeatures, E> {
+    self.select_mux_channel()?;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let mut config = [0u8; (EEPROM_MIRROR_ADDRESS - EEPROM_CLKOUT_ADDRESS + 1) as usize];
+    self.read_multi_registers_raw(EEPROM_CLKOUT_ADDRESS, &mut config)?;
+    let clkout = config[0];
+    let backup = config[(EEPROM_MIRROR_ADDRESS - EEPROM_CLKOUT_ADDRESS) as usize];
+
+    Ok(PowerFeatures {
+      clockout_enabled: 0 != clkout & RegEepromClkoutBits::ClockoutEnableBit as u8,
+      clockout_interrupt_controlled: 0 != control2 & RegControl2Bits::ClockoutIntEnableBit as u8,
+      clockout_rate: ClockoutRate::from_register(clkout),
+      trickle_charge_enabled: 0 != backup & RegEepromMirrorBits::TrickleChargeEnableBit as u8,
+      trickle_charge_limiter: TrickleChargeCurrentLimiter::from_register(backup),
+      backup_switchover_enabled: 0 != backup & RegEepromMirrorBits::BackupSwitchoverDsm as u8,
+      interrupts: InterruptEnables {
+        time_update: 0 != control2 & RegControl2Bits::TimeUpdateIntEnableBit as u8,
+        countdown_timer: 0 != control2 & RegControl2Bits::TimerIntEnableBit as u8,
+        alarm: 0 != control2 & RegControl2Bits::AlarmIntEnableBit as u8,
+        external_event: 0 != control2 & RegControl2Bits::EventIntEnableBit as u8,
+        backup_switchover: 0 != backup & RegEepromMirrorBits::BackupSwitchIntEnableBit as u8,
+      },
+    })
+  }
+
+  /// Apply a one-time correction for drift measured against a reference clock,
+  /// stepping both the BCD date and time registers and the Unix time counter
+  /// by the same whole number of seconds, so they stay consistent with each other.
+  /// - `measured_offset_secs` how many seconds the RTC is ahead of the reference
+  ///   (negative if the RTC is behind)
+  ///
+  /// This is a coarse, whole-second stepping correction: it isn't a frequency trim,
+  /// and rewriting the Seconds register resets the prescaler, discarding the fraction
+  /// of a second elapsed since the last tick.
+  /// If the RTC ticks between reading the time and writing the corrected time back,
+  /// the correction is redone from the later time, so it isn't left a second short.
+  /// Returns the new Unix time, or `Error::OutOfRange` if the corrected time can't be
+  /// represented by the Unix time counter or the BCD registers (2000..2099).
+  /// Returns `Error::Timeout` if the RTC keeps ticking before the correction is written.
+  #[cfg(feature = "chrono")]
+  pub fn apply_drift_correction(&mut self, measured_offset_secs: i64) -> Result<u32, Error<E>> {
+    let (mut datetime, mut unix_time) = self.read_time_and_unix()?;
+    if 0 == measured_offset_secs {
+      return Ok(unix_time);
+    }
+    for _ in 0..MAX_CONSISTENT_READS {
+      let corrected_unix: u32 = (unix_time as i64 - measured_offset_secs)
+        .try_into().map_err(|_| Error::OutOfRange)?;
+      // the offset is now known to be within the Unix counter's range, so this can't overflow
+      let corrected_datetime = datetime.checked_sub_signed(Duration::seconds(measured_offset_secs))
+        .filter(|dt| (2000..=2099).contains(&dt.year()))
+        .ok_or(Error::OutOfRange)?;
+      self.select_mux_channel()?;
+      self.set_date_raw(&corrected_datetime.date())?;
+      self.set_time_raw(&corrected_datetime.time())?;
+
+      // Writing Seconds reset the prescaler, so nothing ticks again until after
+      // the Unix counter is written. If the (not yet rewritten) Unix counter has
+      // moved on from the snapshot, a tick landed before the BCD time was written:
+      // redo the correction from the later time.
+      let unix_now = self.get_unix_time_raw()?;
+      if unix_now == unix_time {
+        self.set_unix_time_raw(corrected_unix)?;
+        return Ok(corrected_unix);
+      }
+      let ticks = unix_now.wrapping_sub(unix_time) as i64;
+      datetime = datetime.checked_add_signed(Duration::seconds(ticks))
+        .ok_or(Error::OutOfRange)?;
+      unix_time = unix_now;
+    }
+    Err(Error::Timeout)
+  }
+
+  /// Read the oscillator compensation settings.
+  /// The RV-3028 has no temperature sensor or temperature compensation status registers:
+  /// its 32.768 kHz crystal is factory calibrated, and the only user adjustable
+  /// compensation is the aging offset (EEOffset), which is read from the RAM mirror
+  /// of the EEPROM Offset register and bit 7 of the EEPROM Backup register.
+  pub fn get_oscillator_status(&mut self) -> Result<OscillatorStatus, E> {
+    let mut read_buf = [0u8; 2];
+    self.read_multi_registers(EEPROM_OFFSET_ADDRESS, &mut read_buf)?;
+    // EEOffset is a 9 bit two's complement value
+    let offset = ((read_buf[0] as i16) << 1) |
+      (0 != read_buf[1] & RegEepromMirrorBits::OffsetLsbBit as u8) as i16;
+    let aging_offset = if offset > 0xFF { offset - 0x200 } else { offset };
+    Ok(OscillatorStatus { aging_offset })
+  }
+
+  /// Read the RTC's Unix time counter as a `std::time::SystemTime`
+  #[cfg(feature = "std")]
+  pub fn to_system_time(&mut self) -> Result<std::time::SystemTime, E> {
+    let unix_time = self.get_unix_time_blocking()?;
+    Ok(std::time::UNIX_EPOCH + std::time::Duration::from_secs(unix_time.into()))
+  }
+
+  /// Set the RTC from a `std::time::SystemTime`, such as `SystemTime::now()`,
+  /// truncated to whole seconds.
+  /// Like `set_datetime`, this sets both the Unix time counter and the BCD registers.
+  /// Returns `Error::OutOfRange` if the time is outside the years 2000 to 2099.
+  #[cfg(all(feature = "std", feature = "chrono"))]
+  pub fn set_from_system_time(&mut self, t: std::time::SystemTime) -> Result<(), Error<E>> {
+    let secs = t.duration_since(std::time::UNIX_EPOCH)
+      .map_err(|_| Error::OutOfRange)?.as_secs();
+    let secs: i64 = secs.try_into().map_err(|_| Error::OutOfRange)?;
+    let datetime = chrono::DateTime::from_timestamp(secs, 0)
+      .map(|dt| dt.naive_utc())
+      .ok_or(Error::OutOfRange)?;
+    self.set_datetime(&datetime)
+  }
+
+  /// Write the two bytes of general purpose User RAM.
+  /// Note that if write protection is enabled and the RTC is locked,
+  /// the write is silently ignored by the RTC: see `set_user_ram_verified`.
+  pub fn set_user_ram(&mut self, data: &[u8; 2]) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.count_transaction();
+    self.i2c.write(RV3028_ADDRESS, &[REG_USER_RAM1, data[0], data[1]])
+  }
+
+  /// Read the two bytes of general purpose User RAM
+  pub fn get_user_ram(&mut self) -> Result<[u8; 2], E> {
+    let mut read_buf = [0u8; 2];
+    self.read_multi_registers(REG_USER_RAM1, &mut read_buf)?;
+    Ok(read_buf)
+  }
+
+  /// Write the two bytes of User RAM, then read them back.
+  /// Returns whether the write took effect: false indicates that the write
+  /// was blocked, eg by write protection.
+  pub fn set_user_ram_verified(&mut self, data: &[u8; 2]) -> Result<bool, E> {
+    self.set_user_ram(data)?;
+    let read_back = self.get_user_ram()?;
+    Ok(read_back == *data)
+  }
+
+  /// Check whether register writes are currently blocked by write protection,
+  /// by writing a different value to the first User RAM byte and reading it back.
+  /// If the write took effect, the original value is written back before returning,
+  /// so the User RAM is unchanged either way.
+  /// The second User RAM byte, used by `power_loss_detected`, isn't touched.
+  /// Returns true if the write was blocked.
+  pub fn probe_write_protection(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let original = self.read_register_raw(REG_USER_RAM1)?;
+    let probe = !original;
+    self.write_register_raw(REG_USER_RAM1, probe)?;
+    let read_back = self.read_register_raw(REG_USER_RAM1)?;
+    if read_back != probe {
+      return Ok(true);
+    }
+    self.write_register_raw(REG_USER_RAM1, original)?;
+    Ok(false)
+  }
+
+  /// Prepare for `power_loss_detected` by writing a magic value to the second User RAM byte.
+  /// This needs to be called once, eg after first setting the time.
+  /// Power loss detection consumes that User RAM byte, so it must not be used for anything else.
+  pub fn init_power_loss_detection(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.write_register_raw(REG_USER_RAM2, POWER_LOSS_MAGIC)
+  }
+
+  /// Check whether the RTC has lost all power (both Vdd and Vbackup) since
+  /// `init_power_loss_detection` or the previous call to this method,
+  /// by checking whether the magic value in the second User RAM byte survived.
+  /// Unlike the PORF flag, this can't be cleared by other software that uses the RTC.
+  /// If a power loss is detected, the magic value is rewritten,
+  /// so that later calls only report subsequent power losses.
+  pub fn power_loss_detected(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let lost = POWER_LOSS_MAGIC != self.read_register_raw(REG_USER_RAM2)?;
+    if lost {
+      self.write_register_raw(REG_USER_RAM2, POWER_LOSS_MAGIC)?;
+    }
+    Ok(lost)
+  }
+
+  /// Toggle whether EVI events trigger on high/rising or low/falling edges
+  pub fn toggle_event_high_low(&mut self, high: bool) -> Result<(), E> {
+    self.set_or_clear_reg_bits(REG_EVENT_CONTROL, RegEventControlBits::EventHighLowBit as u8, high)
+  }
+
+  /// Enable INT pin output when alarm occurs
+  pub fn toggle_alarm_int_enable(&mut self, enable: bool) -> Result<(), E> {
+    self.set_or_clear_reg_bits(REG_CONTROL2, RegControl2Bits::AlarmIntEnableBit as u8, enable)
+  }
 
   /// Toggle whether the RTC outputs a pulse (active low) on INT pin,
   /// when the countdown timer expires.
@@ -612,6 +1975,56 @@ impl<I2C, E> RV3028<I2C>
     self.set_or_clear_reg_bits(REG_CONTROL2, RegControl2Bits::TimeUpdateIntEnableBit as u8, enable)
   }
 
+  /// Report how the INT pin behaves for a given interrupt source.
+  /// The RTC doesn't allow configuring this; it's fixed per source:
+  /// - The Periodic Countdown Timer and Periodic Time Update interrupts
+  ///   pulse INT low, and INT is automatically released after a short time (tRTN).
+  /// - All other interrupts hold INT low until their flag is cleared
+  ///   (no automatic cancellation).
+  ///
+  /// Hosts should use edge detection for pulsed sources;
+  /// either edge or level detection works for latched sources,
+  /// as long as the flag is cleared to release INT.
+  pub fn get_int_output_mode(&self, source: InterruptSource) -> IntOutputMode {
+    match source {
+      InterruptSource::CountdownTimer | InterruptSource::TimeUpdate => IntOutputMode::Pulsed,
+      InterruptSource::Alarm | InterruptSource::ExternalEvent |
+      InterruptSource::BackupSwitchover | InterruptSource::PowerOnReset => IntOutputMode::Latched,
+    }
+  }
+
+  /// Find the interrupt source that is currently asserting the INT pin:
+  /// the highest priority source whose flag is set and whose interrupt is enabled.
+  /// Priority order is: power on reset, backup switchover, external event,
+  /// alarm, countdown timer, then time update.
+  /// Returns None if no enabled interrupt source has its flag set.
+  /// This doesn't clear any flags: see `service_interrupts`.
+  pub fn pending_interrupt_source(&mut self) -> Result<Option<InterruptSource>, E> {
+    self.select_mux_channel()?;
+    let status = self.read_register_raw(REG_STATUS)?;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let clkout = self.read_register_raw(EEPROM_CLKOUT_ADDRESS)?;
+    let backup = self.read_register_raw(EEPROM_MIRROR_ADDRESS)?;
+
+    let sources = [
+      (InterruptSource::PowerOnReset, RegStatusBits::PowerOnResetFlagBit as u8,
+       0 != clkout & RegEepromClkoutBits::PorIntEnableBit as u8),
+      (InterruptSource::BackupSwitchover, RegStatusBits::BackupSwitchFlag as u8,
+       0 != backup & RegEepromMirrorBits::BackupSwitchIntEnableBit as u8),
+      (InterruptSource::ExternalEvent, RegStatusBits::EventFlagBit as u8,
+       0 != control2 & RegControl2Bits::EventIntEnableBit as u8),
+      (InterruptSource::Alarm, RegStatusBits::AlarmFlagBit as u8,
+       0 != control2 & RegControl2Bits::AlarmIntEnableBit as u8),
+      (InterruptSource::CountdownTimer, RegStatusBits::PeriodicTimerFlag as u8,
+       0 != control2 & RegControl2Bits::TimerIntEnableBit as u8),
+      (InterruptSource::TimeUpdate, RegStatusBits::TimeUpdateFlag as u8,
+       0 != control2 & RegControl2Bits::TimeUpdateIntEnableBit as u8),
+    ];
+    Ok(sources.iter()
+      .find(|(_, flag, enabled)| *enabled && 0 != status & flag)
+      .map(|(source, _, _)| *source))
+  }
+
   /// Disable all INT pin output selector bits in RAM, excludes PORIE
   pub fn clear_all_int_out_bits(&mut self) -> Result<(), E> {
     self.select_mux_channel()?;
@@ -630,6 +2043,79 @@ impl<I2C, E> RV3028<I2C>
   }
 
 
+  /// Enable exactly the given INT pin interrupt sources, disabling all others (excludes PORIE).
+  /// Each of Control 2 and the backup configuration register is read once,
+  /// and only written if its interrupt enable bits need to change.
+  pub fn set_interrupt_enables(&mut self, enables: InterruptEnables) -> Result<(), E> {
+    self.select_mux_channel()?;
+    // UIE, TIE, AIE, EIE
+    let control2_bits = [
+      (RegControl2Bits::TimeUpdateIntEnableBit as u8, enables.time_update),
+      (RegControl2Bits::TimerIntEnableBit as u8, enables.countdown_timer),
+      (RegControl2Bits::AlarmIntEnableBit as u8, enables.alarm),
+      (RegControl2Bits::EventIntEnableBit as u8, enables.external_event),
+    ];
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let new_control2 = control2_bits.iter().fold(control2, |val, (bit, enable)| {
+      if *enable { val | bit } else { val & !bit }
+    });
+    if new_control2 != control2 {
+      self.write_register_raw(REG_CONTROL2, new_control2)?;
+    }
+
+    // BSIE
+    let bsie = RegEepromMirrorBits::BackupSwitchIntEnableBit as u8;
+    let backup = self.read_register_raw(EEPROM_MIRROR_ADDRESS)?;
+    let new_backup = if enables.backup_switchover { backup | bsie } else { backup & !bsie };
+    if new_backup != backup {
+      self.write_register_raw(EEPROM_MIRROR_ADDRESS, new_backup)?;
+    }
+    Ok(())
+  }
+
+  /// Silence the INT and CLKOUT pins before entering a low power state,
+  /// leaving timekeeping running. This clears:
+  /// - CLKOE, disabling the clock output
+  /// - CLKIE and the clock interrupt mask bits, so no interrupt can enable CLKOUT
+  /// - UIE, TIE, AIE, EIE, and BSIE, so no interrupt can assert INT (PORIE is left alone)
+  ///
+  /// Each register is read once, and only written if any of its output bits are set.
+  /// The bits that were set are remembered so that `restore_outputs` can reenable them on wake.
+  /// CLKOE and BSIE are changed in the RAM mirror of the EEPROM configuration only,
+  /// and are reloaded from EEPROM on power on reset.
+  pub fn enter_low_power_outputs(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    let mut saved = self.saved_outputs.unwrap_or([0; LOW_POWER_OUTPUT_BITS.len()]);
+    for (i, (reg, bits)) in LOW_POWER_OUTPUT_BITS.iter().enumerate() {
+      let val = self.read_register_raw(*reg)?;
+      if 0 != val & bits {
+        self.write_register_raw(*reg, val & !bits)?;
+        // merge with anything saved by a previous call without a restore in between
+        saved[i] |= val & bits;
+      }
+    }
+    self.saved_outputs = Some(saved);
+    Ok(())
+  }
+
+  /// Reenable the INT and CLKOUT output bits that were disabled by `enter_low_power_outputs`,
+  /// leaving any other changes made to those registers since then in place.
+  /// Does nothing if `enter_low_power_outputs` hasn't been called since the last restore.
+  pub fn restore_outputs(&mut self) -> Result<(), E> {
+    let saved = match self.saved_outputs {
+      Some(saved) => saved,
+      None => return Ok(()),
+    };
+    self.select_mux_channel()?;
+    for ((reg, _), bits) in LOW_POWER_OUTPUT_BITS.iter().zip(saved) {
+      if 0 != bits {
+        self.set_reg_bits_raw(*reg, bits)?;
+      }
+    }
+    self.saved_outputs = None;
+    Ok(())
+  }
+
   /// Clear all of the status registers that indicate whether
   /// various conditions have triggered
   pub fn clear_all_status_flags(&mut self) -> Result<(), E> {
@@ -646,8 +2132,45 @@ impl<I2C, E> RV3028<I2C>
 
   }
 
+  /// Read the Status register as-is, without masking or clearing any flags.
+  /// Intended for debugging or custom flag handling. From MSB to LSB the bits are
+  /// EEBUSY, CLKF, BSF, UF, TF, AF, EVF, PORF: see `StatusFlags` for a decoded view.
+  pub fn get_status_raw(&mut self) -> Result<u8, E> {
+    self.select_mux_channel()?;
+    self.read_register_raw(REG_STATUS)
+  }
+
+  /// Service pending interrupts from an interrupt handler:
+  /// reads the Status register once, clears exactly the flags that were set,
+  /// and returns those flags (the sources that fired).
+  /// Flags that become set after the Status register is read are left untouched,
+  /// because writing 1 to a status flag has no effect.
+  ///
+  /// This is also the way to release the INT pin on hosts with edge-triggered
+  /// GPIO interrupts: INT stays low until every latched flag is cleared,
+  /// so no further falling edge arrives while any flag remains set.
+  pub fn service_interrupts(&mut self) -> Result<StatusFlags, E> {
+    self.select_mux_channel()?;
+    let status = self.read_register_raw(REG_STATUS)?;
+    let fired = status & !(RegStatusBits::EepromBusyBit as u8);
+    if 0 != fired {
+      self.write_register_raw(REG_STATUS, !fired)?;
+    }
+    Ok(StatusFlags::from_register(fired))
+  }
+
+  /// Drain all latched interrupt flags, releasing the INT pin, and report which had fired.
+  /// This is the same as `service_interrupts`, named for use at startup or shutdown,
+  /// eg to release INT before waiting for the next falling edge.
+  pub fn drain_and_report(&mut self) -> Result<StatusFlags, E> {
+    self.service_interrupts()
+  }
+
   /// - `int_enable` enables INT output on the periodic time updates
-  pub fn configure_periodic_time_update(&mut self, minutes: bool, int_enable: bool) -> Result<(), E> {
+  ///
+  /// Returns true if a time update flag (UF) that was set while reconfiguring was cleared,
+  /// so that it doesn't cause a spurious first interrupt.
+  pub fn configure_periodic_time_update(&mut self, minutes: bool, int_enable: bool) -> Result<bool, E> {
     self.select_mux_channel()?;
 
     // 1. Initialize bits UIE and UF to 0.
@@ -670,7 +2193,66 @@ impl<I2C, E> RV3028<I2C>
     self.set_or_clear_reg_bits_raw(
       REG_CONTROL2, RegControl2Bits::TimeUpdateIntEnableBit as u8, int_enable)?;
 
-    Ok(())
+    // Clear UF again in case an update set it between clearing UF and re-setting UIE
+    let suppressed = 0 != self.check_and_clear_bits(
+      REG_STATUS, RegStatusBits::TimeUpdateFlag as u8)?;
+    Ok(suppressed)
+  }
+
+  /// Configure the periodic time update: interval, INT output, and clock output.
+  /// This follows the same procedure as `configure_periodic_time_update`,
+  /// additionally setting the CUIE bit in the clock interrupt mask.
+  /// Returns true if a spurious time update flag (UF) was cleared, as for
+  /// `configure_periodic_time_update`.
+  pub fn set_time_update_config(&mut self, config: &TimeUpdateConfig) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    // UIE and UF clear
+    self.clear_reg_bits_raw(
+      REG_CONTROL2, RegControl2Bits::TimeUpdateIntEnableBit as u8)?;
+    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::TimeUpdateFlag as u8)?;
+    // USEL set/clear
+    self.set_or_clear_reg_bits_raw(
+      REG_CONTROL1, RegControl1Bits::UselBit as u8, config.minute_interval)?;
+    // CUIE set/clear
+    self.set_or_clear_reg_bits_raw(
+      REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnUpdateBit as u8,
+      config.clockout_on_update)?;
+    // UIE re-set
+    self.set_or_clear_reg_bits_raw(
+      REG_CONTROL2, RegControl2Bits::TimeUpdateIntEnableBit as u8, config.interrupt_enabled)?;
+    // Clear UF again in case an update set it between clearing UF and re-setting UIE
+    let suppressed = 0 != self.check_and_clear_bits(
+      REG_STATUS, RegStatusBits::TimeUpdateFlag as u8)?;
+    Ok(suppressed)
+  }
+
+  /// Read back the periodic time update configuration
+  pub fn get_time_update_config(&mut self) -> Result<TimeUpdateConfig, E> {
+    self.select_mux_channel()?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let clock_int_mask = self.read_register_raw(REG_CLOCK_INTERRUPT_MASK)?;
+    Ok(TimeUpdateConfig {
+      minute_interval: 0 != control1 & RegControl1Bits::UselBit as u8,
+      interrupt_enabled: 0 != control2 & RegControl2Bits::TimeUpdateIntEnableBit as u8,
+      clockout_on_update: 0 != clock_int_mask & RegClockIntMaskBits::ClockoutOnUpdateBit as u8,
+    })
+  }
+
+  /// Poll the Periodic Time Update Flag (UF) `max_checks` times,
+  /// clearing it each time it's found set, and return how many
+  /// update events were observed over the polling window.
+  /// The RTC doesn't count missed updates, so if this returns fewer events
+  /// than the expected number of seconds (or minutes) elapsed,
+  /// the caller is polling too slowly and missing ticks.
+  pub fn poll_time_updates(&mut self, max_checks: u32) -> Result<u32, E> {
+    let mut update_count = 0;
+    for _ in 0..max_checks {
+      if 0 != self.check_and_clear_bits(REG_STATUS, RegStatusBits::TimeUpdateFlag as u8)? {
+        update_count += 1;
+      }
+    }
+    Ok(update_count)
   }
 
 
@@ -683,6 +2265,15 @@ impl<I2C, E> RV3028<I2C>
     Ok(alarm_flag_set)
   }
 
+  /// Clear the alarm flag (AF) without reporting whether it was set,
+  /// which releases the INT pin if it was asserted by the alarm.
+  /// Leaves AIE and the alarm settings untouched:
+  /// intended as a fast acknowledgement from an interrupt handler.
+  pub fn acknowledge_alarm(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::AlarmFlagBit as u8)
+  }
+
   /// All-in-one method to set an alarm:
   /// See the App Note section "Procedure to use the Alarm Interrupt"
   /// Note only date/weekday, hour, minute are supported
@@ -690,11 +2281,204 @@ impl<I2C, E> RV3028<I2C>
   /// - `match_day` indicates whether the day (or weekday) should be matched for the alarm
   /// - `match_hour` indicates whether the hour should be matched for the alarm
   /// - `match_minute` indicates whether the minutes should be matched for the alarm
+  ///
+  /// Returns `Error::OutOfRange` if the minute, hour, or day can't be represented
+  /// by the alarm registers.
+  /// Returns `Error::AlarmMatchesNothing` if none of the match flags are set,
+  /// since that alarm would fire every minute: use `set_alarm_every_minute` if that's intended.
+  #[cfg(feature = "chrono")]
   pub fn set_alarm(&mut self, datetime: &NaiveDateTime,
-                   weekday: Option<Weekday>, match_day: bool, match_hour: bool, match_minute: bool) -> Result<(), E> {
+                   weekday: Option<Weekday>, match_day: bool, match_hour: bool, match_minute: bool) -> Result<(), Error<E>> {
+    if !(match_day || match_hour || match_minute) {
+      return Err(Error::AlarmMatchesNothing);
+    }
+
+    // Validate all alarm values before touching any registers
+    let minute_val = Self::alarm_register_value(
+      datetime.time().minute() as u8, 0, 59, match_minute)?;
+    let hour_val = Self::alarm_register_value(
+      datetime.time().hour() as u8, 0, 23, match_hour)?;
+    let day_val =
+      if let Some(inner_weekday) = weekday {
+        Self::alarm_register_value(
+          Self::weekday_to_register(inner_weekday, self.weekday_base), 0, 6, match_day)?
+      }
+      else {
+        Self::alarm_register_value(datetime.date().day() as u8, 1, 31, match_day)?
+      };
+
+    self.select_mux_channel()?;
+    self.write_alarm_registers_raw(minute_val, hour_val, day_val, weekday.is_some())?;
+    Ok(())
+  }
+
+  /// Set the alarm directly from raw (binary, not BCD) register values,
+  /// without building a `NaiveDateTime`.
+  /// Each field is either `Some(value)` to match that value, or `None` to ignore
+  /// that field when matching the alarm.
+  /// - `minute` minute to match, 0..59
+  /// - `hour` hour to match, 0..23
+  /// - `weekday_or_date` weekday (0..6) if `is_weekday` is true, otherwise date of month (1..31)
+  /// - `is_weekday` selects a weekday alarm rather than a date alarm
+  ///
+  /// Returns `Error::OutOfRange` if any value can't be represented by the alarm registers.
+  pub fn set_alarm_raw(&mut self, minute: Option<u8>, hour: Option<u8>,
+                       weekday_or_date: Option<u8>, is_weekday: bool) -> Result<(), Error<E>> {
+    let minute_val = match minute {
+      Some(val) => Self::alarm_register_value(val, 0, 59, true)?,
+      None => ALARM_NO_WATCH_FLAG,
+    };
+    let hour_val = match hour {
+      Some(val) => Self::alarm_register_value(val, 0, 23, true)?,
+      None => ALARM_NO_WATCH_FLAG,
+    };
+    let (day_min, day_max) = if is_weekday { (0, 6) } else { (1, 31) };
+    let day_val = match weekday_or_date {
+      Some(val) => Self::alarm_register_value(val, day_min, day_max, true)?,
+      None => ALARM_NO_WATCH_FLAG,
+    };
 
     self.select_mux_channel()?;
-    // Initialize AF to 0; AIE/AlarmIntEnableBit is managed independently
+    self.write_alarm_registers_raw(minute_val, hour_val, day_val, is_weekday)?;
+    Ok(())
+  }
+
+  /// Set an alarm that fires every minute, by not matching any alarm field.
+  pub fn set_alarm_every_minute(&mut self) -> Result<(), Error<E>> {
+    self.set_alarm_raw(None, None, None, false)
+  }
+
+  /// Switch the alarm between date mode (`date_mode` true) and weekday mode,
+  /// by setting or clearing the WADA bit.
+  /// The weekday/date alarm register holds a value for the previous mode,
+  /// which would be misinterpreted in the new mode (eg weekday 3 read as the 3rd of the month),
+  /// so this also disables weekday/date matching (sets its "no watch" flag)
+  /// until the caller rewrites the alarm, eg with `set_alarm_raw`.
+  /// The minute and hour alarm settings are unchanged.
+  pub fn set_alarm_mode(&mut self, date_mode: bool) -> Result<(), E> {
+    self.select_mux_channel()?;
+    // Stop matching the stale value before its interpretation changes
+    self.set_reg_bits_raw(REG_WEEKDAY_DATE_ALARM, ALARM_NO_WATCH_FLAG)?;
+    self.set_or_clear_reg_bits_raw(REG_CONTROL1, RegControl1Bits::WadaBit as u8, date_mode)
+  }
+
+  /// Set a weekday alarm without building a `NaiveDateTime`.
+  /// - `weekday` weekday to match
+  /// - `time` hour and minute to match, or `None` to match only the weekday
+  ///
+  /// Note that matching only the weekday fires the alarm every minute of that day.
+  #[cfg(feature = "chrono")]
+  pub fn set_weekday_alarm(&mut self, weekday: Weekday, time: Option<NaiveTime>) -> Result<(), Error<E>> {
+    let weekday_val = Self::weekday_to_register(weekday, self.weekday_base);
+    self.set_alarm_raw(time.map(|t| t.minute() as u8), time.map(|t| t.hour() as u8),
+                       Some(weekday_val), true)
+  }
+
+  /// Set a monthly date alarm, matching the day of month, hour, and minute.
+  /// - `day_of_month` day of month to match, 1..31
+  /// - `time` hour and minute to match: seconds are ignored
+  ///
+  /// Note that the alarm matches the date register directly, so eg
+  /// a `day_of_month` of 31 won't fire at all in months with fewer than 31 days.
+  /// Returns `Error::OutOfRange` if `day_of_month` is not in 1..31.
+  #[cfg(feature = "chrono")]
+  pub fn set_monthly_alarm(&mut self, day_of_month: u8, time: &NaiveTime) -> Result<(), Error<E>> {
+    self.set_alarm_raw(Some(time.minute() as u8), Some(time.hour() as u8),
+                       Some(day_of_month), false)
+  }
+
+  /// Set a date alarm at a relative offset from the current RTC time,
+  /// eg "alarm in 5 minutes".
+  /// The current time is read from the same calendar registers the alarm is matched against.
+  /// - `match_day`, `match_hour`, `match_minute` as for `set_alarm`
+  ///
+  /// Returns the computed alarm datetime.
+  /// Note that the alarm only has minute resolution: seconds are ignored.
+  #[cfg(feature = "chrono")]
+  pub fn set_alarm_in(&mut self, from_now: Duration,
+                      match_day: bool, match_hour: bool, match_minute: bool)
+    -> Result<NaiveDateTime, Error<E>>
+  {
+    let now = self.read_bcd_datetime()?;
+    let alarm_dt = now.checked_add_signed(from_now).ok_or(Error::OutOfRange)?;
+    self.set_alarm(&alarm_dt, None, match_day, match_hour, match_minute)?;
+    Ok(alarm_dt)
+  }
+
+  /// Set an alarm as for `set_alarm`, then read back the alarm registers,
+  /// returning the alarm settings as actually stored by the RTC.
+  /// This combines setting the alarm and verifying it in one call.
+  #[cfg(feature = "chrono")]
+  pub fn set_alarm_confirmed(&mut self, datetime: &NaiveDateTime,
+                             weekday: Option<Weekday>, match_day: bool, match_hour: bool, match_minute: bool)
+    -> Result<AlarmConfig, Error<E>>
+  {
+    self.set_alarm(datetime, weekday, match_day, match_hour, match_minute)?;
+    Ok(self.get_alarm_config()?)
+  }
+
+  /// Set an alarm as for `set_alarm`, and enable the alarm interrupt (AIE),
+  /// intended to fire only once.
+  /// The RTC re-triggers the alarm on every match (eg a minute-matched alarm fires every hour),
+  /// so to make the alarm fire exactly once, the caller must call `check_and_disarm_alarm`
+  /// after it fires, either by polling or from the INT handler.
+  #[cfg(feature = "chrono")]
+  pub fn arm_one_shot_alarm(&mut self, datetime: &NaiveDateTime,
+                            weekday: Option<Weekday>, match_day: bool, match_hour: bool, match_minute: bool)
+    -> Result<(), Error<E>>
+  {
+    self.set_alarm(datetime, weekday, match_day, match_hour, match_minute)?;
+    self.set_reg_bits_raw(REG_CONTROL2, RegControl2Bits::AlarmIntEnableBit as u8)?;
+    Ok(())
+  }
+
+  /// Check whether the alarm has fired, and if so, clear the alarm flag (AF)
+  /// and disable the alarm interrupt (AIE), so the alarm doesn't fire again.
+  /// See `arm_one_shot_alarm`.
+  /// Returns true if the alarm had fired.
+  pub fn check_and_disarm_alarm(&mut self) -> Result<bool, E> {
+    let fired = 0 != self.check_and_clear_bits(
+      REG_STATUS, RegStatusBits::AlarmFlagBit as u8)?;
+    if fired {
+      self.clear_reg_bits_raw(REG_CONTROL2, RegControl2Bits::AlarmIntEnableBit as u8)?;
+    }
+    Ok(fired)
+  }
+
+  // Write already-encoded values to the alarm registers, skips the mux.
+  // `hour_val` is encoded for 24 hour mode, and is re-encoded here if the RTC is in 12 hour mode.
+  fn write_alarm_registers_raw(&mut self, minute_val: u8, mut hour_val: u8, day_val: u8,
+                               is_weekday: bool) -> Result<(), E> {
+    // Momentarily disable AIE while reprogramming, so that a stale or
+    // spurious alarm doesn't assert INT; AIE is restored to its prior state below,
+    // even if reprogramming fails part way
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    if 0 != control2 & RegControl2Bits::TwelveHourModeBit as u8 {
+      let hour = Self::hour_from_register(hour_val, false);
+      hour_val = (hour_val & ALARM_NO_WATCH_FLAG) | Self::hour_to_register(hour, true);
+    }
+    let alarm_int_enabled = 0 != control2 & RegControl2Bits::AlarmIntEnableBit as u8;
+    if alarm_int_enabled {
+      self.write_register_raw(REG_CONTROL2, control2 & !(RegControl2Bits::AlarmIntEnableBit as u8))?;
+    }
+
+    let result = self.program_alarm_registers_raw(minute_val, hour_val, day_val, is_weekday);
+
+    if alarm_int_enabled {
+      // report the first error, from reprogramming if it failed
+      let restored = self.set_reg_bits_raw(REG_CONTROL2, RegControl2Bits::AlarmIntEnableBit as u8);
+      result.and(restored)
+    }
+    else {
+      result
+    }
+  }
+
+  // The body of `write_alarm_registers_raw`, run while AIE is disabled, skips the mux.
+  // `hour_val` is already encoded for the RTC's current hour mode.
+  fn program_alarm_registers_raw(&mut self, minute_val: u8, hour_val: u8, day_val: u8,
+                                 is_weekday: bool) -> Result<(), E> {
+    // Initialize AF to 0
     self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::AlarmFlagBit as u8)?;
 
     // Procedure suggested by App Notes:
@@ -707,41 +2491,219 @@ impl<I2C, E> RV3028<I2C>
 
     // Clear WADA for weekday alarm, or set for date alarm
     self.set_or_clear_reg_bits_raw(
-      REG_CONTROL1, RegControl1Bits::WadaBit as u8, !weekday.is_some())?;
+      REG_CONTROL1, RegControl1Bits::WadaBit as u8, !is_weekday)?;
 
-    let bcd_minute = Self::bin_to_bcd(datetime.time().minute() as u8);
-    self.write_register_raw(REG_MINUTES_ALARM,
-                        if match_minute { bcd_minute }
-                        else { ALARM_NO_WATCH_FLAG | bcd_minute })?;
+    self.write_register_raw(REG_MINUTES_ALARM, minute_val)?;
+    self.write_register_raw(REG_HOURS_ALARM, hour_val)?;
+    self.write_register_raw(REG_WEEKDAY_DATE_ALARM, day_val)?;
 
-    let bcd_hour = Self::bin_to_bcd(datetime.time().hour() as u8);
-    self.write_register_raw(REG_HOURS_ALARM,
-                        if match_hour { bcd_hour  }
-                        else { ALARM_NO_WATCH_FLAG | bcd_hour })?;
+    // Clear AF again in case the above setting process immediately triggered the alarm
+    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::AlarmFlagBit as u8)
+  }
 
-    if let Some(inner_weekday) = weekday {
-      let bcd_weekday = Self::bin_to_bcd(inner_weekday as u8);
-      self.write_register_raw(REG_WEEKDAY_DATE_ALARM,
-                          if match_day { bcd_weekday }
-                          else { ALARM_NO_WATCH_FLAG | bcd_weekday }
-      )?;
-    }
-    else {
-      let bcd_day = Self::bin_to_bcd(datetime.date().day() as u8);
-      self.write_register_raw(REG_WEEKDAY_DATE_ALARM,
-                          if match_day { bcd_day }
-                          else { ALARM_NO_WATCH_FLAG | bcd_day })?;
+  // Check an alarm value against the limits of its register, then BCD-encode it,
+  // setting the "no watch" flag if the value should not be matched
+  fn alarm_register_value(value: u8, min: u8, max: u8, watch: bool) -> Result<u8, Error<E>> {
+    if value < min || value > max {
+      return Err(Error::OutOfRange);
     }
+    let bcd_value = Self::bin_to_bcd(value);
+    Ok(if watch { bcd_value } else { ALARM_NO_WATCH_FLAG | bcd_value })
+  }
 
-    // Clear AF again in case the above setting process immediately triggered the alarm
-    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::AlarmFlagBit as u8)?;
+  /// Read the alarm settings as an `AlarmConfig`.
+  /// The alarm hour is decoded as 0..23, whether the RTC is in 12 or 24 hour mode.
+  pub fn get_alarm_config(&mut self) -> Result<AlarmConfig, E> {
+    self.select_mux_channel()?;
+    let mut read_buf = [0u8; 3];
+    self.read_multi_registers_raw(REG_MINUTES_ALARM, &mut read_buf)?;
+    // Control 1 and Control 2
+    let mut control = [0u8; 2];
+    self.read_multi_registers_raw(REG_CONTROL1, &mut control)?;
+    Ok(Self::alarm_config_from_registers(&read_buf, control[0], control[1]))
+  }
+
+  /// Read the raw Minutes Alarm, Hours Alarm, and Weekday/Date Alarm register values,
+  /// including the alarm enable (no watch) bits, for exact comparison with
+  /// the values the alarm setting methods should have written.
+  pub fn get_alarm_registers(&mut self) -> Result<(u8, u8, u8), E> {
+    let mut read_buf = [0u8; 3];
+    self.read_multi_registers(REG_MINUTES_ALARM, &mut read_buf)?;
+    Ok((read_buf[0], read_buf[1], read_buf[2]))
+  }
 
+  /// Change which fields the configured alarm matches, leaving the alarm values untouched.
+  /// For example, disabling `match_hour` turns a daily alarm into an hourly alarm.
+  /// - `match_day`, `match_hour`, `match_minute` as for `set_alarm`
+  ///
+  /// Note a field that wasn't matched may hold an arbitrary value:
+  /// check `get_alarm_config` before enabling a match on such a field.
+  /// Returns `Error::AlarmMatchesNothing` if none of the match flags are set.
+  pub fn set_alarm_match_flags(&mut self, match_day: bool, match_hour: bool, match_minute: bool)
+    -> Result<(), Error<E>>
+  {
+    if !(match_day || match_hour || match_minute) {
+      return Err(Error::AlarmMatchesNothing);
+    }
+    self.select_mux_channel()?;
+    let mut regs = [0u8; 3];
+    self.read_multi_registers_raw(REG_MINUTES_ALARM, &mut regs)?;
+    let watch = |raw: u8, matched: bool| {
+      if matched { raw & !ALARM_NO_WATCH_FLAG } else { raw | ALARM_NO_WATCH_FLAG }
+    };
+    let write_buf = [
+      REG_MINUTES_ALARM, // select the first register
+      watch(regs[0], match_minute),
+      watch(regs[1], match_hour),
+      watch(regs[2], match_day),
+    ];
+    self.count_transaction();
+    self.i2c.write(RV3028_ADDRESS, &write_buf)?;
     Ok(())
   }
 
+  /// Check whether the alarm configuration can ever fire: at least one field must be
+  /// matched, and each matched field must hold a valid value for the current mode,
+  /// ie a weekday (0..6) in weekday mode (WADA = 0), or a date (1..31) in date mode,
+  /// and an hour valid for 12 or 24 hour mode.
+  /// For example, this catches a weekday value left in place after switching to date mode.
+  /// Returns true if the alarm configuration is consistent.
+  pub fn validate_alarm_config(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let mut regs = [0u8; 3];
+    self.read_multi_registers_raw(REG_MINUTES_ALARM, &mut regs)?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    let is_weekday = 0 == control1 & RegControl1Bits::WadaBit as u8;
+    let twelve_hour = 0 != control2 & RegControl2Bits::TwelveHourModeBit as u8;
+
+    // Whether a field is unmatched, or matches a valid BCD value in the given range
+    let valid = |raw: u8, value_mask: u8, min: u8, max: u8| {
+      let value = raw & value_mask;
+      0 != raw & ALARM_NO_WATCH_FLAG ||
+        (Self::is_valid_bcd(value) && (min..=max).contains(&Self::bcd_to_bin(value)))
+    };
+    let (hour_mask, hour_min, hour_max) = if twelve_hour { (0x1F, 1, 12) } else { (0x3F, 0, 23) };
+    let (day_min, day_max) = if is_weekday { (0, 6) } else { (1, 31) };
+    let any_matched = regs.iter().any(|raw| 0 == raw & ALARM_NO_WATCH_FLAG);
+    Ok(any_matched &&
+      valid(regs[0], 0x7F, 0, 59) &&
+      valid(regs[1], hour_mask, hour_min, hour_max) &&
+      valid(regs[2], 0x3F, day_min, day_max))
+  }
+
+  // Decode the three alarm register values, using the WADA bit of the Control 1 register value
+  // and the 12_24 bit of the Control 2 register value
+  fn alarm_config_from_registers(regs: &[u8; 3], control1: u8, control2: u8) -> AlarmConfig {
+    let decode = |raw: u8| {
+      if 0 == raw & ALARM_NO_WATCH_FLAG { Some(Self::bcd_to_bin(raw)) } else { None }
+    };
+    let twelve_hour = 0 != control2 & RegControl2Bits::TwelveHourModeBit as u8;
+    AlarmConfig {
+      minute: decode(regs[0]),
+      hour: decode(regs[1]).map(|_| Self::hour_from_register(regs[1], twelve_hour)),
+      weekday_or_date: decode(regs[2]),
+      is_weekday: 0 == control1 & RegControl1Bits::WadaBit as u8,
+    }
+  }
+
+  /// Calculate the next datetime at which the configured alarm will fire,
+  /// based on the current time in the RTC's BCD timekeeping registers.
+  /// Returns None if the alarm can never match (for example, a date alarm for the 32nd).
+  #[cfg(feature = "chrono")]
+  pub fn next_alarm_datetime(&mut self) -> Result<Option<NaiveDateTime>, Error<E>> {
+    let now = self.read_bcd_datetime()?;
+    let alarm = self.get_alarm_config()?;
+    Ok(Self::next_alarm_after(&now, &alarm, self.weekday_base))
+  }
+
+  /// Calculate how long until the configured alarm fires,
+  /// based on the current time in the RTC's BCD timekeeping registers.
+  /// Useful for planning how long a host may sleep.
+  /// Returns None if the alarm can never match.
+  #[cfg(feature = "chrono")]
+  pub fn time_until_alarm(&mut self) -> Result<Option<Duration>, Error<E>> {
+    let now = self.read_bcd_datetime()?;
+    let alarm = self.get_alarm_config()?;
+    Ok(Self::next_alarm_after(&now, &alarm, self.weekday_base).map(|next| next - now))
+  }
+
+  /// Calculate the next several datetimes at which the configured alarm will fire,
+  /// based on the current time in the RTC's BCD timekeeping registers.
+  /// - `count` how many fire times to calculate, at most `out.len()`
+  /// - `out` filled with the fire times, in order
+  ///
+  /// The alarm fires at the start of every minute where all the matched fields agree,
+  /// so the recurrence follows from which fields are matched:
+  /// - minute only: hourly
+  /// - hour and minute: daily
+  /// - weekday, hour, and minute: weekly
+  /// - date, hour, and minute: monthly, skipping months without that date
+  /// - hour only, or day only: every minute of that hour or day
+  ///
+  /// Returns how many fire times were calculated, which is fewer than `count`
+  /// only if the alarm can never match.
+  #[cfg(feature = "chrono")]
+  pub fn upcoming_alarms(&mut self, count: usize, out: &mut [NaiveDateTime]) -> Result<usize, Error<E>> {
+    let mut now = self.read_bcd_datetime()?;
+    let alarm = self.get_alarm_config()?;
+    let count = count.min(out.len());
+    for (i, slot) in out[..count].iter_mut().enumerate() {
+      match Self::next_alarm_after(&now, &alarm, self.weekday_base) {
+        Some(next) => {
+          *slot = next;
+          now = next;
+        }
+        None => return Ok(i),
+      }
+    }
+    Ok(count)
+  }
+
+  // Find the first datetime after `now` at which the alarm fires.
+  // The alarm fires at the start of any minute where all the matched fields agree.
+  // `weekday_base` is the weekday counted as 0 by the Weekday counter.
+  #[cfg(feature = "chrono")]
+  fn next_alarm_after(now: &NaiveDateTime, alarm: &AlarmConfig,
+                      weekday_base: Weekday) -> Option<NaiveDateTime> {
+    let start = now.date().and_hms_opt(now.hour(), now.minute(), 0)? + Duration::minutes(1);
+    // A date alarm on the 31st may be nearly two months away
+    for day_offset in 0..62 {
+      let date = start.date() + Duration::days(day_offset);
+      if let Some(day) = alarm.weekday_or_date {
+        let date_val =
+          if alarm.is_weekday { Self::weekday_to_register(date.weekday(), weekday_base) }
+          else { date.day() as u8 };
+        if date_val != day {
+          continue;
+        }
+      }
+      let first_day = 0 == day_offset;
+      let start_hour = if first_day { start.hour() } else { 0 };
+      for hour in start_hour..24 {
+        if matches!(alarm.hour, Some(alarm_hour) if alarm_hour as u32 != hour) {
+          continue;
+        }
+        let start_minute = if first_day && hour == start.hour() { start.minute() } else { 0 };
+        for minute in start_minute..60 {
+          if matches!(alarm.minute, Some(alarm_minute) if alarm_minute as u32 != minute) {
+            continue;
+          }
+          return date.and_hms_opt(hour, minute, 0);
+        }
+      }
+    }
+    None
+  }
+
   /// Read the alarm settings
   /// Matches are flag settings for whether the alarm should match day, hour, minute
   ///
+  /// Fields that aren't matched hold don't-care values, so they are reported as
+  /// placeholders from `NaiveDateTime::UNIX_EPOCH` (the 1st, hour 0, minute 0),
+  /// and the weekday as `None`. See `get_alarm_config` for a result that
+  /// represents unmatched fields as `None` directly.
+  #[cfg(feature = "chrono")]
   pub fn get_alarm_datetime_wday_matches(&mut self)
     -> Result<(NaiveDateTime, Option<Weekday>, bool, bool, bool), E> {
 
@@ -753,7 +2715,6 @@ impl<I2C, E> RV3028<I2C>
 
     let raw_hour = self.read_register_raw(REG_HOURS_ALARM)?;
     let match_hour = 0 == (raw_hour & ALARM_NO_WATCH_FLAG);
-    let hour = Self::bcd_to_bin(0x7F & raw_hour);
 
     let raw_minutes = self.read_register_raw(REG_MINUTES_ALARM)?;
     let match_minutes = 0 == (raw_minutes & ALARM_NO_WATCH_FLAG);
@@ -762,20 +2723,26 @@ impl<I2C, E> RV3028<I2C>
     let mut weekday = None;
 
     let wada_state = self.read_register_raw(REG_CONTROL1)? & RegControl1Bits::WadaBit as u8;
+    let hour = Self::hour_from_register(raw_hour, self.is_twelve_hour_mode_raw()?);
 
+    // Unmatched fields hold stale values, and any field may hold an out-of-range value,
+    // eg a date of 0: keep the placeholder's value for those fields
+    let base = NaiveDateTime::UNIX_EPOCH;
     let dt =
-      if 0 == wada_state {
+      if !match_day {
+        base
+      }
+      else if 0 == wada_state {
         // weekday alarm
-        weekday = Some(Weekday::try_from(day).unwrap());
-        NaiveDateTime::UNIX_EPOCH.with_hour(hour as u32).unwrap()
-          .with_minute(minutes as u32).unwrap()
+        weekday = Self::register_to_weekday(day, self.weekday_base);
+        base
       }
       else {
         // date alarm
-        NaiveDateTime::UNIX_EPOCH.with_day(day as u32).unwrap()
-          .with_hour(hour as u32).unwrap()
-          .with_minute(minutes as u32).unwrap()
+        base.with_day(day as u32).unwrap_or(base)
       };
+    let dt = if match_hour { dt.with_hour(hour as u32).unwrap_or(dt) } else { dt };
+    let dt = if match_minutes { dt.with_minute(minutes as u32).unwrap_or(dt) } else { dt };
 
     Ok((dt, weekday, match_day, match_hour, match_minutes))
   }
@@ -798,12 +2765,176 @@ impl<I2C, E> RV3028<I2C>
   }
 
 
+  // Read the event count and the timestamp of one logged event
+  #[cfg(feature = "chrono")]
+  fn read_event_timestamp(&mut self) -> Result<(u32, Option<NaiveDateTime>), Error<E>> {
+    // Read the seven raw Time Stamp Function registers in one go,
+    // repeating until two successive reads agree: an event logged
+    // during a read could otherwise leave a torn timestamp.
+    // With TSOW = 1 and a fast event source this may never settle, so give up eventually.
+    self.select_mux_channel()?;
+    let mut read_buf:[u8;7] = [0u8;7];
+    self.read_multi_registers_raw(REG_COUNT_EVENTS_TS, &mut read_buf)?;
+    let mut consistent = false;
+    for _ in 1..MAX_CONSISTENT_READS {
+      let mut check_buf:[u8;7] = [0u8;7];
+      self.read_multi_registers_raw(REG_COUNT_EVENTS_TS, &mut check_buf)?;
+      if check_buf == read_buf {
+        consistent = true;
+        break;
+      }
+      read_buf = check_buf;
+    }
+    if !consistent {
+      return Err(Error::Timeout);
+    }
+
+    // Per the datasheet, Count TS is a plain 8-bit binary counter that
+    // saturates at 255, unlike the BCD timestamp registers that follow it
+    let count = read_buf[0];
+
+    // The timestamp registers may hold invalid values, eg if logging was just
+    // enabled: report no timestamp rather than an invalid one
+    let odt = {
+      if count > 0 && read_buf[1..].iter().all(|val| Self::is_valid_bcd(*val)) {
+        let seconds = Self::bcd_to_bin(read_buf[1]);
+        let minutes = Self::bcd_to_bin(read_buf[2]);
+        let hours = Self::bcd_to_bin(read_buf[3]);
+        let date = Self::bcd_to_bin(read_buf[4]);
+        let month = Self::bcd_to_bin(read_buf[5]);
+        let year:i32 = Self::bcd_to_bin(read_buf[6]) as i32 + 2000;
+        NaiveDate::from_ymd_opt(year, month as u32, date as u32)
+          .and_then(|date| date.and_hms_opt(hours as u32, minutes as u32, seconds as u32))
+      }
+      else {
+        None
+      }
+    };
+
+    Ok((count as u32, odt))
+  }
+
+  /// Read the event log, including whether the logged timestamp is of the
+  /// first event or the most recent event, according to the TSOW bit
+  /// (see `toggle_time_stamp_overwrite`).
+  #[cfg(feature = "chrono")]
+  pub fn get_event_log(&mut self) -> Result<EventLog, Error<E>> {
+    self.select_mux_channel()?;
+    let event_control = self.read_register_raw(REG_EVENT_CONTROL)?;
+    let (count, timestamp) = self.read_event_timestamp()?;
+    Ok(EventLog {
+      count,
+      timestamp,
+      is_latest: 0 != event_control & RegEventControlBits::TimeStampOverwriteBit as u8,
+      source:
+        if 0 != event_control & RegEventControlBits::TimeStampSourceBit as u8 { TS_EVENT_SOURCE_BSF }
+        else { TS_EVENT_SOURCE_EVI },
+    })
+  }
+
+  /// Read the time of the logged backup switchover (switching to Vbackup),
+  /// or `None` if no switchover has been logged.
+  /// This requires the time stamp source to be set to backup switchover (TSS = 1),
+  /// eg with `config_timestamp_logging(TS_EVENT_SOURCE_BSF, ...)`:
+  /// otherwise the time stamp registers hold the time of an EVI pin event instead.
+  /// Whether this is the first or latest switchover depends on the TSOW setting.
+  #[cfg(feature = "chrono")]
+  pub fn get_last_backup_switchover(&mut self) -> Result<Option<NaiveDateTime>, Error<E>> {
+    let (_count, timestamp) = self.read_event_timestamp()?;
+    Ok(timestamp)
+  }
+
   /// Enables or disables default CLKOUT behavior
   pub fn toggle_plain_clockout(&mut self, enable: bool) -> Result<(), E> {
     self.select_mux_channel()?;
     // TODO self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::ClockIntFlagBit as u8)?;
     self.set_or_clear_reg_bits_raw(
-      EEPROM_MIRROR_ADDRESS, RegEepromMirrorBits::ClockoutOutputEnableBit as u8, enable)
+      EEPROM_CLKOUT_ADDRESS, RegEepromClkoutBits::ClockoutEnableBit as u8, enable)
+  }
+
+  /// Enable the clock output on the CLKOUT pin at the given rate,
+  /// setting CLKOE and the FD bits with a single register write.
+  /// This changes the RAM mirror of the EEPROM Clkout register,
+  /// which is lost on power on reset unless written to EEPROM.
+  pub fn enable_clockout(&mut self, rate: ClockoutRate) -> Result<(), E> {
+    self.select_mux_channel()?;
+    let mut clkout = self.read_register_raw(EEPROM_CLKOUT_ADDRESS)?;
+    clkout &= !(RegEepromClkoutBits::FrequencySelectionBits as u8);
+    clkout |= RegEepromClkoutBits::ClockoutEnableBit as u8 | rate as u8;
+    self.write_register_raw(EEPROM_CLKOUT_ADDRESS, clkout)
+  }
+
+  /// Read the raw EEPROM Clkout register byte (CLKOE, CLKSY, PORIE, and the FD bits),
+  /// from its RAM mirror.
+  /// The Clkout configuration is loaded from EEPROM on power on reset,
+  /// so checking the exact bits is useful when a stored configuration seems wrong.
+  pub fn get_clockout_register(&mut self) -> Result<u8, E> {
+    self.select_mux_channel()?;
+    self.read_register_raw(EEPROM_CLKOUT_ADDRESS)
+  }
+
+  /// Read the configured clock output frequency (the FD bits).
+  /// This is the rate CLKOUT runs at when it's enabled, see `enable_clockout`.
+  pub fn get_clockout_rate(&mut self) -> Result<ClockoutRate, E> {
+    let clkout = self.get_clockout_register()?;
+    Ok(ClockoutRate::from_register(clkout))
+  }
+
+  /// Mirror the Periodic Countdown Timer on the CLKOUT pin, in one call.
+  /// This combines:
+  /// - CLKOE = 1 with the FD bits set to `ClockoutRate::CountdownTimer` (110),
+  ///   so CLKOUT outputs the predefined countdown timer interrupt
+  /// - CTIE = 1 in the clock interrupt mask, so the countdown timer interrupt
+  ///   also drives the clock output
+  ///
+  /// The countdown timer itself must be configured and enabled separately,
+  /// eg with `config_countdown_timer`.
+  /// Like `enable_clockout`, this changes the RAM mirror of the EEPROM Clkout register.
+  pub fn route_countdown_to_clockout(&mut self) -> Result<(), E> {
+    self.enable_clockout(ClockoutRate::CountdownTimer)?;
+    self.set_reg_bits_raw(
+      REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnPctBit as u8)
+  }
+
+  /// Configure the alarm to wake the host and optionally signal on CLKOUT, in one call.
+  /// - `int_enable` whether the alarm asserts the INT pin (AIE)
+  /// - `clockout` if given, the alarm also enables the clock output at this rate:
+  ///   CLKIE and CAIE are set, and CLKOE is cleared so that CLKOUT is only driven
+  ///   after the alarm fires (until the Clock Output Interrupt Flag is cleared),
+  ///   rather than free-running. If `None`, CAIE is cleared so the alarm doesn't drive CLKOUT,
+  ///   and the rest of the clock output configuration is left unchanged.
+  ///
+  /// Like `enable_clockout`, this changes the RAM mirror of the EEPROM Clkout register.
+  pub fn config_alarm_wake(&mut self, int_enable: bool, clockout: Option<ClockoutRate>) -> Result<(), E> {
+    self.select_mux_channel()?;
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let clkie = RegControl2Bits::ClockoutIntEnableBit as u8;
+    let mut control2 = self.read_register_raw(REG_CONTROL2)?;
+    control2 = if int_enable { control2 | aie } else { control2 & !aie };
+    match clockout {
+      Some(rate) => {
+        let mut clkout = self.read_register_raw(EEPROM_CLKOUT_ADDRESS)?;
+        clkout &= !(RegEepromClkoutBits::ClockoutEnableBit as u8 |
+          RegEepromClkoutBits::FrequencySelectionBits as u8);
+        self.write_register_raw(EEPROM_CLKOUT_ADDRESS, clkout | rate as u8)?;
+        self.set_reg_bits_raw(
+          REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnAlarmBit as u8)?;
+        self.write_register_raw(REG_CONTROL2, control2 | clkie)
+      }
+      None => {
+        self.clear_reg_bits_raw(
+          REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnAlarmBit as u8)?;
+        self.write_register_raw(REG_CONTROL2, control2)
+      }
+    }
+  }
+
+  /// Disable the clock output on the CLKOUT pin (clears CLKOE),
+  /// leaving the configured rate unchanged.
+  pub fn disable_clockout(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.clear_reg_bits_raw(
+      EEPROM_CLKOUT_ADDRESS, RegEepromClkoutBits::ClockoutEnableBit as u8)
   }
 
   /// Enables or disables interrupt-controlled CLKOUT
@@ -813,6 +2944,15 @@ impl<I2C, E> RV3028<I2C>
     REG_CONTROL2,  RegControl2Bits::ClockoutIntEnableBit as u8, enable)
   }
 
+  /// Whether clock output is controlled by interrupts (CLKIE is set),
+  /// ie CLKOUT is only driven when an interrupt configured in the
+  /// Clock Interrupt Mask register occurs, rather than free-running.
+  pub fn is_clockout_interrupt_controlled(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let control2 = self.read_register_raw(REG_CONTROL2)?;
+    Ok(0 != control2 & RegControl2Bits::ClockoutIntEnableBit as u8)
+  }
+
   // Configure the Periodic Countdown Timer prior to the next countdown.
   fn config_pct_raw(&mut self, value: u16, freq: TimerClockFreq, repeat: bool ) -> Result<(), E> {
     let value_high: u8 = ((value >> 8) as u8) & 0x0F;
@@ -827,24 +2967,33 @@ impl<I2C, E> RV3028<I2C>
 
     // write to REG_TIMER_VALUE0 and REG_TIMER_VALUE1
     let write_buf = [ REG_TIMER_VALUE0, value_low, value_high];
+    self.count_transaction();
     self.i2c.write(RV3028_ADDRESS, &write_buf)?;
 
     self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::PeriodicTimerFlag as u8)?;
+    self.last_countdown_value = None;
     Ok(())
   }
 
   const MAX_PCT_TICKS: u16 = 0x0FFF; // 4095
+  #[cfg(feature = "chrono")]
   const PCT_MILLIS_PERIOD:i64 = 15; // 15.625 ms period
+  #[cfg(feature = "chrono")]
   const PCT_MICROS_PERIOD:i64 = 244; // 244.14 μs period
 
+  #[cfg(feature = "chrono")]
   const MAX_PCT_COUNT:i64 = Self::MAX_PCT_TICKS as i64;
+  #[cfg(feature = "chrono")]
   const MAX_PCT_MILLIS:i64 = Self::MAX_PCT_COUNT * Self::PCT_MILLIS_PERIOD;
+  #[cfg(feature = "chrono")]
   const MAX_PCT_MICROS:i64 = Self::MAX_PCT_COUNT * Self::PCT_MICROS_PERIOD;
+  #[cfg(feature = "chrono")]
   const PCT_MILLIS_SECOND_BARRIER: i64 =  Self::PCT_MILLIS_PERIOD*(1000/Self::PCT_MILLIS_PERIOD);
 
   // Calculate the closest clock frequency and
   // number of ticks to match the requested duration using the
   // Periodic Countdown Timer (PCT)
+  #[cfg(feature = "chrono")]
   fn pct_ticks_and_rate_for_duration(duration: &Duration) -> (u16, TimerClockFreq, Duration)
   {
     let whole_minutes = duration.num_minutes();
@@ -888,6 +3037,7 @@ impl<I2C, E> RV3028<I2C>
   /// Returns the estimated actual duration (which may vary from the requested duration
   /// dur to discrete RTC clock ticks).
   /// - `start`: If true, start the countdown
+  #[cfg(feature = "chrono")]
   pub fn config_countdown_timer(&mut self, duration: &Duration,
                                 repeat: bool, start: bool
   ) -> Result<Duration, E> {
@@ -903,6 +3053,28 @@ impl<I2C, E> RV3028<I2C>
     Ok(estimated)
   }
 
+  /// Prepare the Periodic Countdown Timer for a countdown of whole minutes,
+  /// always using the 1/60 Hz timer clock (the lowest power setting),
+  /// and optionally start the countdown.
+  /// - `minutes`: number of minutes to count down, 1..4095
+  /// - `repeat`: If true, the countdown timer will repeat as a periodic timer.
+  /// - `start`: If true, start the countdown
+  ///
+  /// Returns `Error::OutOfRange` if `minutes` can't be represented by the timer.
+  pub fn set_countdown_minutes(&mut self, minutes: u16, repeat: bool, start: bool)
+    -> Result<(), Error<E>>
+  {
+    if minutes == 0 || minutes > Self::MAX_PCT_TICKS {
+      return Err(Error::OutOfRange);
+    }
+    self.select_mux_channel()?;
+    self.config_pct_raw(minutes, TimerClockFreq::HertzSixtieth, repeat)?;
+    if start {
+      self.set_reg_bits_raw(REG_CONTROL1, RegControl1Bits::TimerEnableBit as u8)?;
+    }
+    Ok(())
+  }
+
   /// Set whether the Periodic Countdown Timer mode is repeating (periodic) or one-shot.
   /// - `enable`: If true, starts the timer countdown. If false, stops the timer.
   pub fn toggle_countdown_timer(&mut self, enable: bool)  -> Result<(), E> {
@@ -910,6 +3082,22 @@ impl<I2C, E> RV3028<I2C>
       REG_CONTROL1, RegControl1Bits::TimerEnableBit as u8, enable)
   }
 
+  /// Check whether the Periodic Countdown Timer is configured to repeat (periodic),
+  /// rather than to run once (one-shot), by reading the TRPT bit.
+  pub fn is_countdown_repeating(&mut self) -> Result<bool, E> {
+    self.select_mux_channel()?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    Ok(0 != control1 & RegControl1Bits::TimerRepeatBit as u8)
+  }
+
+  /// Read the Periodic Countdown Timer clock frequency (TD bits),
+  /// which gives the duration of each tick counted by `get_countdown_value`.
+  pub fn get_timer_clock_freq(&mut self) -> Result<TimerClockFreq, E> {
+    self.select_mux_channel()?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    Ok(TimerClockFreq::from_register(control1))
+  }
+
   /// Check whether countdown timer has finished counting down, and clear it
   pub fn check_and_clear_countdown(&mut self) -> Result<bool, E> {
     let flag_set = 0 != self.check_and_clear_bits(
@@ -917,6 +3105,58 @@ impl<I2C, E> RV3028<I2C>
     Ok(flag_set)
   }
 
+  /// Poll the countdown timer flag (TF) until it is set, clear it, and return
+  /// the time elapsed between starting to poll and seeing TF set.
+  /// - `clock` caller-provided monotonic clock, in microseconds
+  /// - `max_polls` maximum number of status register reads before giving up
+  ///
+  /// The first call after starting the timer measures a partial period:
+  /// for a repeating timer, each later back-to-back call measures one full period,
+  /// to within the i2c polling latency.
+  /// Returns `Error::Timeout` if TF isn't set within `max_polls` reads.
+  #[cfg(feature = "chrono")]
+  pub fn await_countdown_period(&mut self, clock: impl Fn() -> i64, max_polls: u32)
+    -> Result<Duration, Error<E>>
+  {
+    let start = clock();
+    for _ in 0..max_polls {
+      if self.check_and_clear_countdown()? {
+        return Ok(Duration::microseconds(clock() - start));
+      }
+    }
+    Err(Error::Timeout)
+  }
+
+  /// Check whether a countdown has completed since the previous call, and clear TF.
+  ///
+  /// TF is set each time the countdown reaches zero, and stays set until cleared:
+  /// in one-shot mode the timer then stops, while in repeat mode the timer value is
+  /// reloaded and TF is set again at the end of every period (only the INT pulse
+  /// is released automatically).
+  /// A TF that is set just after the status register is read could be missed,
+  /// so this also reads the timer value, and treats an increase since the previous
+  /// call (a reload) as a completed countdown.
+  pub fn countdown_fired_since_last_check(&mut self) -> Result<bool, E> {
+    let timer_flag = RegStatusBits::PeriodicTimerFlag as u8;
+    self.select_mux_channel()?;
+    let flag_set = 0 != self.read_register_raw(REG_STATUS)? & timer_flag;
+    if flag_set {
+      // writing 1 to the other flags has no effect, so this clears only TF
+      self.write_register_raw(REG_STATUS, !timer_flag)?;
+    }
+    let mut read_buf = [0u8; 2];
+    self.read_multi_registers_raw(REG_TIMER_STATUS0, &mut read_buf)?;
+    let value = u16::from_le_bytes(read_buf);
+    let reloaded = matches!(self.last_countdown_value, Some(last) if value > last);
+    self.last_countdown_value = Some(value);
+    if reloaded && !flag_set {
+      // TF was set by this reload after the status register was read: clear it,
+      // so that the same countdown isn't reported again by the next call
+      self.write_register_raw(REG_STATUS, !timer_flag)?;
+    }
+    Ok(flag_set || reloaded)
+  }
+
   /// Read the current value of the Periodic Countdown Timer,
   /// which is only valid after the timer has been enabled.
   /// The meaning of the value depends on the configured TimerClockFreq
@@ -927,6 +3167,93 @@ impl<I2C, E> RV3028<I2C>
     Ok(value)
   }
 
+  /// Check that the Periodic Countdown Timer is actually running, by reading the
+  /// timer value, calling `clock_delay`, then reading the value again.
+  /// - `clock_delay` caller-provided delay, which should be longer than
+  ///   one period of the configured TimerClockFreq
+  ///
+  /// Returns true if the value decreased, or increased because a repeating timer
+  /// was reloaded in between. Returns false if the value is unchanged, eg if
+  /// the timer isn't enabled, a one-shot countdown has finished,
+  /// or the delay was too short for the timer clock.
+  pub fn is_countdown_progressing(&mut self, mut clock_delay: impl FnMut()) -> Result<bool, E> {
+    let before = self.get_countdown_value()?;
+    clock_delay();
+    let after = self.get_countdown_value()?;
+    Ok(before != after)
+  }
+
+  /// Read back the programmed Periodic Countdown Timer preset (the reload value),
+  /// as opposed to `get_countdown_value` which reads the live countdown.
+  /// Returns the 12-bit preset, in ticks of the configured TimerClockFreq.
+  pub fn get_countdown_preset(&mut self) -> Result<u16, E> {
+    let mut read_buf = [0u8;2];
+    self.read_multi_registers(REG_TIMER_VALUE0, &mut read_buf)?;
+    Ok(u16::from_le_bytes(read_buf) & Self::MAX_PCT_TICKS)
+  }
+
+  /// Read the time remaining in the current countdown of the Periodic Countdown Timer:
+  /// the live countdown value multiplied by the period of the configured TimerClockFreq.
+  /// The result is quantized to whole timer ticks, so the actual time remaining may be
+  /// up to one tick period less than this. As for `get_countdown_value`,
+  /// this is only meaningful while the timer is enabled: see `time_until_countdown_expiry`.
+  #[cfg(feature = "chrono")]
+  pub fn get_countdown_remaining(&mut self) -> Result<Duration, E> {
+    self.select_mux_channel()?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    let mut read_buf = [0u8; 2];
+    self.read_multi_registers_raw(REG_TIMER_STATUS0, &mut read_buf)?;
+    let ticks = u16::from_le_bytes(read_buf) & Self::MAX_PCT_TICKS;
+    Ok(Self::countdown_ticks_to_duration(ticks, TimerClockFreq::from_register(control1)))
+  }
+
+  /// Time until the Periodic Countdown Timer next expires (reaches zero),
+  /// computed as remaining ticks × tick period as for `get_countdown_remaining`.
+  /// Returns None if the timer isn't enabled (TE = 0), since the countdown value
+  /// is then meaningless.
+  #[cfg(feature = "chrono")]
+  pub fn time_until_countdown_expiry(&mut self) -> Result<Option<Duration>, E> {
+    self.select_mux_channel()?;
+    let control1 = self.read_register_raw(REG_CONTROL1)?;
+    if 0 == control1 & RegControl1Bits::TimerEnableBit as u8 {
+      return Ok(None);
+    }
+    let mut read_buf = [0u8; 2];
+    self.read_multi_registers_raw(REG_TIMER_STATUS0, &mut read_buf)?;
+    let ticks = u16::from_le_bytes(read_buf) & Self::MAX_PCT_TICKS;
+    Ok(Some(Self::countdown_ticks_to_duration(ticks, TimerClockFreq::from_register(control1))))
+  }
+
+  // Convert a number of countdown timer ticks to a Duration, at nanosecond resolution
+  #[cfg(feature = "chrono")]
+  fn countdown_ticks_to_duration(ticks: u16, freq: TimerClockFreq) -> Duration {
+    let ticks = ticks as i64;
+    match freq {
+      TimerClockFreq::Hertz4096 => Duration::nanoseconds(ticks * 1_000_000_000 / 4096),
+      TimerClockFreq::Hertz64 => Duration::microseconds(ticks * 1_000_000 / 64),
+      TimerClockFreq::Hertz1 => Duration::seconds(ticks),
+      TimerClockFreq::HertzSixtieth => Duration::minutes(ticks),
+    }
+  }
+
+  /// Set only the Periodic Countdown Timer value (the reload value),
+  /// leaving the clock frequency, repeat, and enable settings untouched.
+  /// For a running periodic timer, this adjusts the period of later countdowns:
+  /// the new value is loaded when the current countdown reaches zero.
+  /// - `ticks`: number of timer clock ticks, up to 4095
+  ///
+  /// Returns `Error::OutOfRange` if `ticks` can't be represented by the timer.
+  pub fn set_countdown_value(&mut self, ticks: u16) -> Result<(), Error<E>> {
+    if ticks > Self::MAX_PCT_TICKS {
+      return Err(Error::OutOfRange);
+    }
+    self.select_mux_channel()?;
+    let bytes = ticks.to_le_bytes();
+    self.count_transaction();
+    self.i2c.write(RV3028_ADDRESS, &[REG_TIMER_VALUE0, bytes[0], bytes[1]])?;
+    Ok(())
+  }
+
   // check and clear a flag
   fn check_and_clear_bits(&mut self, reg: u8, bits: u8) -> Result<u8, E> {
     self.select_mux_channel()?;
@@ -943,7 +3270,21 @@ impl<I2C, E> RV3028<I2C>
   /// - `rising` whether edge detection is on rising edge / high level
   /// - `int_enable` whether events detected on EVI pin should generate an interrupt on INT pin
   /// - `filtering` 00..11 time filtering
+  ///
+  /// Returns `Error::OutOfRange` if `filtering` is greater than 0b11,
+  /// which would otherwise corrupt the neighboring bits of the Event Control register.
   pub fn config_ext_event_detection(
+    &mut self, rising: bool, int_enable: bool, filtering: u8, clockout_enable: bool) -> Result<(), Error<E>>
+  {
+    if filtering > 0b11 {
+      return Err(Error::OutOfRange);
+    }
+    self.write_ext_event_detection(rising, int_enable, filtering, clockout_enable)?;
+    Ok(())
+  }
+
+  // Configure event detection on the EVI pin, with an already-validated `filtering` value
+  fn write_ext_event_detection(
     &mut self, rising: bool, int_enable: bool, filtering: u8, clockout_enable: bool) -> Result<(), E>
   {
     self.select_mux_channel()?;
@@ -972,7 +3313,7 @@ impl<I2C, E> RV3028<I2C>
     // 5. Select EDGE DETECTION (ET = 00) or LEVEL DETECTION WITH FILTERING (ET ≠ 00).
     self.clear_reg_bits_raw(REG_EVENT_CONTROL,RegEventControlBits::EventFilteringTimeBits as u8)?;
     if 0 != filtering {
-      // TODO verify this sets the correct filtering
+      // ET bits are bits 5:4, see `get_event_filter`
       self.set_reg_bits_raw(REG_EVENT_CONTROL, filtering << 4)?;
     }
 
@@ -988,9 +3329,147 @@ impl<I2C, E> RV3028<I2C>
     Ok(())
   }
 
+  /// Set what triggers an event on the EVI pin, setting the EHL and ET bits consistently.
+  /// Other event detection settings are unchanged.
+  pub fn set_event_trigger(&mut self, trigger: EventTrigger) -> Result<(), E> {
+    let (high, filter) = match trigger {
+      EventTrigger::RisingEdge => (true, EventFilterTime::NoFilter),
+      EventTrigger::FallingEdge => (false, EventFilterTime::NoFilter),
+      EventTrigger::HighLevel(filter) => (true, filter),
+      EventTrigger::LowLevel(filter) => (false, filter),
+    };
+    self.select_mux_channel()?;
+    let mut event_control = self.read_register_raw(REG_EVENT_CONTROL)?;
+    event_control &= !(RegEventControlBits::EventHighLowBit as u8 |
+      RegEventControlBits::EventFilteringTimeBits as u8);
+    if high {
+      event_control |= RegEventControlBits::EventHighLowBit as u8;
+    }
+    event_control |= (filter as u8) << 4;
+    self.write_register_raw(REG_EVENT_CONTROL, event_control)
+  }
+
+  /// Read the event filtering time (ET bits) for the EVI pin
+  pub fn get_event_filter(&mut self) -> Result<EventFilterTime, E> {
+    self.select_mux_channel()?;
+    let event_control = self.read_register_raw(REG_EVENT_CONTROL)?;
+    let filter = match (event_control & RegEventControlBits::EventFilteringTimeBits as u8) >> 4 {
+      0b00 => EventFilterTime::NoFilter,
+      0b01 => EventFilterTime::Ms3_9,
+      0b10 => EventFilterTime::Ms15_6,
+      _ => EventFilterTime::Ms125,
+    };
+    Ok(filter)
+  }
+
+  // Set up time stamp logging for events, see `EventTimeStampLogger::config_timestamp_logging`
+  fn write_timestamp_logging_config(
+    &mut self, evt_source: u8, overwrite: bool,  start:bool) -> Result<(), E>
+  {
+    self.select_mux_channel()?;
+
+    // Pause listening for events
+    // 1. Initialize bits TSE to 0.
+    self.clear_reg_bits_raw(REG_CONTROL2,
+                            RegControl2Bits::TimeStampEnableBit as u8)?;
+
+    // 2. Clear EVF and BSF
+    self.clear_reg_bits_raw(
+      REG_STATUS, RegStatusBits::EventFlagBit as u8 | RegStatusBits::BackupSwitchFlag as u8)?;
+
+    // 3. Set TSS bit to
+    // External Event Interrupt function (TSS = 0) or the
+    // Automatic Backup Switchover Interrupt function (TSS = 1)
+    // as time stamp source and initialize the appropriate function
+    let enable_bsf = evt_source == TS_EVENT_SOURCE_BSF;
+    self.set_or_clear_reg_bits_raw(
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampSourceBit as u8, enable_bsf)?;
+
+    // 6. Set TSOW bit to 1 if the last occurred event has to be recorded and TS registers are overwritten.
+    self.set_or_clear_reg_bits_raw(
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampOverwriteBit as u8, overwrite)?;
+
+    // 7. Write 1 to TSR bit, to clear all Time Stamp registers to 0x00
+    self.set_reg_bits_raw(
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampResetBit as u8)?;
+
+    // 9. Set TSE bit to 1 if you want to enable the Time Stamp function.
+    // see also: toggle_timestamp_logging
+    self.set_or_clear_reg_bits_raw(
+      REG_CONTROL2, RegControl2Bits::TimeStampEnableBit as u8, start)?;
+
+    // 1. Initialize bits TSE and EIE to 0.
+    // 2. Clear flag EVF and BSF to 0.
+    // 3. Set TSS bit to 0 to select External Event on EVI pin as Time Stamp and Interrupt source.
+    // 6. Set TSOW bit to 1 to record the last occurred event (and TS registers are overwritten).
+    // 7. Write 1 to TSR bit, to reset all Time Stamp registers to 00h. Bit TSR always returns 0 when read.
+    // 9. Set TSE bit to 1 if you want to enable the Time Stamp function.
+    // pin.
+    Ok(())
+  }
+
+  /// Start counting and timestamping events on the EVI pin, in one call:
+  /// configures edge/level detection, selects EVI as the time stamp source,
+  /// resets the event log, and enables time stamp logging.
+  /// - `rising` whether detection is on rising edge / high level
+  /// - `filter` event filtering time (`EventFilterTime::NoFilter` for edge detection)
+  /// - `log_latest` whether to log the most recent event rather than the first
+  ///
+  /// The event interrupt (EIE) and clock output on events (CEIE) are left as they were,
+  /// see `config_ext_event_detection` to change them.
+  pub fn start_event_capture(
+    &mut self, rising: bool, filter: EventFilterTime, log_latest: bool) -> Result<(), E>
+  {
+    self.select_mux_channel()?;
+    let int_enable =
+      0 != self.read_register_raw(REG_CONTROL2)? & RegControl2Bits::EventIntEnableBit as u8;
+    let clockout_enable = 0 != self.read_register_raw(REG_CLOCK_INTERRUPT_MASK)?
+      & RegClockIntMaskBits::ClockoutOnExtEvtBit as u8;
+    self.write_ext_event_detection(rising, int_enable, filter as u8, clockout_enable)?;
+    // this also resets the log (TSR) before starting
+    self.write_timestamp_logging_config(TS_EVENT_SOURCE_EVI, log_latest, true)
+  }
+
+  /// Stop event capture and return the event subsystem to an idle state:
+  /// disables time stamp logging (TSE), the event interrupt (EIE),
+  /// and clock output on events (CEIE), clears the event flag (EVF),
+  /// and resets the time stamp registers.
+  pub fn disable_event_capture(&mut self) -> Result<(), E> {
+    self.select_mux_channel()?;
+    self.clear_reg_bits_raw(REG_CONTROL2,
+                            RegControl2Bits::TimeStampEnableBit as u8 |
+                              RegControl2Bits::EventIntEnableBit as u8)?;
+    self.clear_reg_bits_raw(
+      REG_CLOCK_INTERRUPT_MASK, RegClockIntMaskBits::ClockoutOnExtEvtBit as u8)?;
+    self.clear_reg_bits_raw(REG_STATUS, RegStatusBits::EventFlagBit as u8)?;
+    self.set_reg_bits_raw(
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampResetBit as u8)
+  }
+
 }
 
 
+/// Read the Unix time from several RV3028 devices sharing one i2c bus behind a mux,
+/// in a single tight sweep to minimize the time skew between readings.
+/// - `mux_addr` i2c address of the mux
+/// - `channels` mux channel selectors, one per RV3028
+///
+/// Returns the Unix times read, in the same order as `channels`.
+pub fn read_muxed_times<I2C, E, const N: usize>(bus: &mut I2C, mux_addr: u8, channels: &[u8; N])
+  -> Result<[u32; N], E>
+  where
+    I2C: Write<Error = E> + WriteRead<Error = E>,
+{
+  let mut times = [0u32; N];
+  for (time, chan) in times.iter_mut().zip(channels.iter()) {
+    bus.write(mux_addr, &[*chan])?;
+    let mut read_buf = [0u8; 4];
+    bus.write_read(RV3028_ADDRESS, &[REG_UNIX_TIME_0], &mut read_buf)?;
+    *time = u32::from_le_bytes(read_buf);
+  }
+  Ok(times)
+}
+
 pub trait EventTimeStampLogger {
   /// Error type
   type Error;
@@ -1000,6 +3479,10 @@ pub trait EventTimeStampLogger {
   fn toggle_timestamp_logging(&mut self, enable: bool) -> Result<(), Self::Error>;
 
   /// clear out any existing logged event timestamps
+  /// Note that this also zeroes the event count: the hardware resets the count and
+  /// the time stamp registers together (TSR), and they are read-only,
+  /// so there's no way to discard the time stamp while keeping the count.
+  /// To keep accumulating a count, read it before resetting and add it to later counts.
   fn reset_timestamp_log(&mut self) -> Result<(), Self::Error>;
 
   /// Setup time stamp logging for events
@@ -1012,6 +3495,7 @@ pub trait EventTimeStampLogger {
 
   /// Get event count -- the number of events that have been logged since enabling logging
   /// Returns the count of events since last reset, and the datetime of one event
+  #[cfg(feature = "chrono")]
   fn get_event_count_and_datetime(&mut self) -> Result<(u32, Option<NaiveDateTime>), Self::Error>;
 
   /// Enable or disable event time stamp overwriting
@@ -1023,11 +3507,12 @@ pub trait EventTimeStampLogger {
   fn set_event_timestamp_source(&mut self, source: u8) -> Result<(), Self::Error>;
 }
 
+#[cfg(feature = "chrono")]
 impl<I2C, E> DateTimeAccess for  RV3028<I2C>
   where
     I2C: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>,
 {
-  type Error = E;
+  type Error = Error<E>;
 
   /// This particular RTC's timestamps wrap at 0xFFFF_FFFF, around the year 2106.
   /// It doesn't support:
@@ -1038,24 +3523,38 @@ impl<I2C, E> DateTimeAccess for  RV3028<I2C>
     Ok(NaiveDateTime::from_timestamp_opt(unix_timestamp.into(), 0).unwrap())
   }
 
-  /// This implementation assumes (but doesn't verify)
-  /// that the caller is setting the RTC datetime to values within its range (from 2000 to 2099).
+  /// Returns `Error::OutOfRange`, without writing anything, for datetimes outside
+  /// the years 2000 to 2099, which are all the BCD registers can hold.
   /// The RTC doesn't support leap year corrections beyond 2099,
   /// and the internal Year BCD register only runs from 0..99 (for 2000..2099).
   /// This method resets the internal prescaler pipeline, which means that
   /// subsecond counters are zeroed, when it writes to the Seconds register.
   /// This assists with clock synchronization with external clocks.
+  ///
+  /// Registers are written in this order:
+  /// 1. the BCD date registers: Weekday, Date, Month, Year
+  /// 2. the BCD time registers: Seconds, Minutes, Hours
+  /// 3. the Unix time counter (which doesn't reset the prescaler).
+  ///
+  /// Writing Seconds resets the prescaler, so the BCD time starts a fresh second,
+  /// and the Unix counter is written within that second, before either of them ticks.
+  /// To set only the BCD registers, use `set_bcd_datetime_only`.
   fn set_datetime(&mut self, datetime: &NaiveDateTime) -> Result<(), Self::Error> {
-    let unix_timestamp: u32 = datetime.timestamp().try_into().unwrap();
+    let unix_timestamp: u32 = Some(datetime)
+      .filter(|dt| (2000..=2099).contains(&dt.year()))
+      .ok_or(Error::OutOfRange)?
+      .timestamp().try_into().map_err(|_| Error::OutOfRange)?;
     self.select_mux_channel()?;
     // unix timestamp counter is stored in registers separate from everything else:
     // this method tries to align both, because the unix timestamp is not
     // used by eg the Event or Alarm interrupts
-    self.set_unix_time_raw(unix_timestamp)?;
     self.set_date_raw(&datetime.date())?;
-    // this must come last because writing to the seconds register resets
-    // the upper stage of the prescaler
+    // writing to the seconds register resets the upper stage of the prescaler,
+    // so the next tick is a full second away...
     self.set_time_raw(&datetime.time())?;
+    // ...which leaves time to write the unix counter before either of them ticks,
+    // so that both hold the same second. Writing the unix counter doesn't reset the prescaler.
+    self.set_unix_time_raw(unix_timestamp)?;
     Ok(())
   }
 
@@ -1064,109 +3563,50 @@ impl<I2C, E> EventTimeStampLogger for  RV3028<I2C>
   where
     I2C: Write<Error = E> + Read<Error = E> + WriteRead<Error = E>
 {
-  type Error = E;
+  type Error = Error<E>;
 
 
   fn toggle_timestamp_logging(&mut self, enable: bool) -> Result<(), Self::Error> {
     self.select_mux_channel()?;
-    self.set_or_clear_reg_bits_raw(REG_CONTROL2, RegControl2Bits::TimeStampEnableBit as u8, enable)
+    self.set_or_clear_reg_bits_raw(REG_CONTROL2, RegControl2Bits::TimeStampEnableBit as u8, enable)?;
+    Ok(())
   }
 
   fn reset_timestamp_log(&mut self) -> Result<(), Self::Error> {
     self.select_mux_channel()?;
     self.set_reg_bits_raw(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampResetBit as u8)
-
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampResetBit as u8)?;
+    Ok(())
   }
 
   fn config_timestamp_logging(
-    &mut self, evt_source: u8, overwrite: bool,  start:bool) -> Result<(), E>
+    &mut self, evt_source: u8, overwrite: bool,  start:bool) -> Result<(), Self::Error>
   {
-    self.select_mux_channel()?;
-
-    // Pause listening for events
-    // 1. Initialize bits TSE to 0.
-    self.clear_reg_bits_raw(REG_CONTROL2,
-                            RegControl2Bits::TimeStampEnableBit as u8)?;
-
-    // 2. Clear EVF and BSF
-    self.clear_reg_bits_raw(
-      REG_STATUS, RegStatusBits::EventFlagBit as u8 | RegStatusBits::BackupSwitchFlag as u8)?;
-
-    // 3. Set TSS bit to
-    // External Event Interrupt function (TSS = 0) or the
-    // Automatic Backup Switchover Interrupt function (TSS = 1)
-    // as time stamp source and initialize the appropriate function
-    let enable_bsf = evt_source == TS_EVENT_SOURCE_BSF;
-    self.set_or_clear_reg_bits_raw(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampSourceBit as u8, enable_bsf)?;
-
-    // 6. Set TSOW bit to 1 if the last occurred event has to be recorded and TS registers are overwritten.
-    self.set_or_clear_reg_bits_raw(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampOverwriteBit as u8, overwrite)?;
-
-    // 7. Write 1 to TSR bit, to clear all Time Stamp registers to 0x00
-    self.set_reg_bits_raw(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampResetBit as u8)?;
-
-    // 9. Set TSE bit to 1 if you want to enable the Time Stamp function.
-    // see also: toggle_timestamp_logging
-    self.set_or_clear_reg_bits_raw(
-      REG_CONTROL2, RegControl2Bits::TimeStampEnableBit as u8, start)?;
-
-    // 1. Initialize bits TSE and EIE to 0.
-    // 2. Clear flag EVF and BSF to 0.
-    // 3. Set TSS bit to 0 to select External Event on EVI pin as Time Stamp and Interrupt source.
-    // 6. Set TSOW bit to 1 to record the last occurred event (and TS registers are overwritten).
-    // 7. Write 1 to TSR bit, to reset all Time Stamp registers to 00h. Bit TSR always returns 0 when read.
-    // 9. Set TSE bit to 1 if you want to enable the Time Stamp function.
-    // pin.
+    self.write_timestamp_logging_config(evt_source, overwrite, start)?;
     Ok(())
   }
 
-  fn get_event_count_and_datetime(&mut self) -> Result<(u32, Option<NaiveDateTime>), Self::Error> {
-    // Read the seven raw Time Stamp Function registers in one go
-    let mut read_buf:[u8;7] = [0u8;7];
-    self.read_multi_registers(REG_COUNT_EVENTS_TS, &mut read_buf)?;
-
-    // Convert BCD values to binary
-    let count = read_buf[0]; // Count is already in binary
-
-    let odt = {
-      if count > 0 {
-        let seconds = Self::bcd_to_bin(read_buf[1]);
-        let minutes = Self::bcd_to_bin(read_buf[2]);
-        let hours = Self::bcd_to_bin(read_buf[3]);
-        let date = Self::bcd_to_bin(read_buf[4]);
-        let month = Self::bcd_to_bin(read_buf[5]);
-        let year:i32 = Self::bcd_to_bin(read_buf[6]) as i32 + 2000;
-        Some(NaiveDate::from_ymd_opt(year as i32, month as u32, date as u32)
-        .expect("YMD")
-          .and_hms_opt(hours as u32, minutes as u32, seconds as u32)
-          .expect("HMS"))
-      }
-      else {
-        None
-      }
-    };
-
-    Ok((count as u32, odt))
+  #[cfg(feature = "chrono")]
+  fn get_event_count_and_datetime(&mut self) -> Result<(u32, Option<NaiveDateTime>), Self::Error> {
+    self.read_event_timestamp()
   }
 
   fn toggle_time_stamp_overwrite(&mut self, enable: bool) -> Result<(), Self::Error> {
     self.set_or_clear_reg_bits(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampOverwriteBit as u8, enable)
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampOverwriteBit as u8, enable)?;
+    Ok(())
   }
 
   fn set_event_timestamp_source(&mut self, source: u8) -> Result<(), Self::Error> {
     let enable = TS_EVENT_SOURCE_BSF == source;
     self.set_or_clear_reg_bits(
-      REG_EVENT_CONTROL, RegEventControlBits::TimeStampSourceBit as u8, enable)
+      REG_EVENT_CONTROL, RegEventControlBits::TimeStampSourceBit as u8, enable)?;
+    Ok(())
   }
 
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
   use super::*;
   use embedded_hal_mock::i2c::{Mock as I2cMock, Transaction as I2cTrans};
@@ -1388,6 +3828,1251 @@ mod tests {
 
   }
 
+  #[test]
+  fn test_set_datetime_twelve_hour_mode() {
+    let dt = NaiveDate::from_ymd_opt(2023, 12, 10).unwrap()
+      .and_hms_opt(14, 30, 0).unwrap();
+    let unix_time: u32 = dt.timestamp().try_into().unwrap();
+    let bytes = unix_time.to_le_bytes();
+    let expectations = [
+      // Sunday, 10 Dec 2023
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY, 0x06, 0x10, 0x12, 0x23]),
+      // RTC is in 12 hour mode
+      I2cTrans::write_read(
+        RV3028_ADDRESS,
+        vec![REG_CONTROL2],
+        vec![RegControl2Bits::TwelveHourModeBit as u8],
+      ),
+      // 2 PM
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x00, 0x30, HOURS_PM_FLAG | 0x02]),
+      // the same second, written right after the seconds register resets the prescaler
+      I2cTrans::write(
+        RV3028_ADDRESS,
+        vec![REG_UNIX_TIME_0, bytes[0], bytes[1], bytes[2], bytes[3]],
+      ),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_datetime(&dt).unwrap();
+    #[cfg(feature = "metrics")]
+    assert_eq!(rv3028.transaction_count(), 4);
+    mock.done();
+  }
+
+  #[test]
+  fn test_set_datetime_beyond_unix_time() {
+    let mut mock = I2cMock::new(&[]);
+    let mut rv3028 = RV3028::new(mock.clone());
+    // The Unix time counter can't represent times after early 2106
+    let dt = NaiveDate::from_ymd_opt(2107, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    assert_eq!(rv3028.set_datetime(&dt), Err(Error::OutOfRange));
+    let dt = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap().and_hms_opt(23, 59, 59).unwrap();
+    assert_eq!(rv3028.set_datetime(&dt), Err(Error::OutOfRange));
+    // Nor can the BCD Year register hold years outside 2000..2099
+    let dt = NaiveDate::from_ymd_opt(1999, 12, 31).unwrap().and_hms_opt(23, 59, 59).unwrap();
+    assert_eq!(rv3028.set_datetime(&dt), Err(Error::OutOfRange));
+    let dt = NaiveDate::from_ymd_opt(2100, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap();
+    assert_eq!(rv3028.set_datetime(&dt), Err(Error::OutOfRange));
+    assert_eq!(NaiveDateTime::from_timestamp_opt(MAX_UNIX_TIME.into(), 0),
+               NaiveDate::from_ymd_opt(2106, 2, 7).unwrap().and_hms_opt(6, 28, 15));
+    mock.done();
+  }
+
+  #[test]
+  fn test_next_alarm_after() {
+    // Sunday, 10 Dec 2023
+    let now = NaiveDate::from_ymd_opt(2023, 12, 10).unwrap()
+      .and_hms_opt(14, 30, 15).unwrap();
+    let at = |d: u32, h: u32, m: u32| {
+      NaiveDate::from_ymd_opt(2023, 12, d).unwrap().and_hms_opt(h, m, 0).unwrap()
+    };
+
+    // every minute
+    let alarm = AlarmConfig { minute: None, hour: None, weekday_or_date: None, is_weekday: false };
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), Some(at(10, 14, 31)));
+
+    // hourly, at 15 minutes past: already passed this hour
+    let alarm = AlarmConfig { minute: Some(15), ..alarm };
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), Some(at(10, 15, 15)));
+
+    // daily at 09:15: already passed today
+    let alarm = AlarmConfig { hour: Some(9), ..alarm };
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), Some(at(11, 9, 15)));
+
+    // weekly on Wednesdays at 09:15
+    let alarm = AlarmConfig { weekday_or_date: Some(2), is_weekday: true, ..alarm };
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), Some(at(13, 9, 15)));
+
+    // monthly on the 10th at 14:30: already fired this minute, so next month
+    let alarm = AlarmConfig {
+      minute: Some(30), hour: Some(14), weekday_or_date: Some(10), is_weekday: false };
+    let next_month = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap().and_hms_opt(14, 30, 0);
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), next_month);
+
+    // a date alarm that can never match
+    let alarm = AlarmConfig { weekday_or_date: Some(32), ..alarm };
+    assert_eq!(TestClass::next_alarm_after(&now, &alarm, Weekday::Mon), None);
+  }
+
+  #[test]
+  fn test_get_hms_reserved_bits() {
+    // reserved high bits set in all three registers
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x80 | 0x59, 0x80 | 0x30, 0xC0 | 0x14]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_hms().unwrap(), (14, 30, 59));
+    mock.done();
+
+    let regs = [0x80 | 0x59, 0x80 | 0x30, 0xC0 | 0x14, 0x06, 0xC0 | 0x10, 0xE0 | 0x12, 0x23];
+    assert_eq!(TestClass::datetime_from_registers(&regs, false),
+               NaiveDate::from_ymd_opt(2023, 12, 10).unwrap().and_hms_opt(14, 30, 59));
+  }
+
+  #[test]
+  fn test_weekday_register_conversion() {
+    assert_eq!(TestClass::weekday_to_register(Weekday::Mon, Weekday::Mon), 0);
+    assert_eq!(TestClass::weekday_to_register(Weekday::Sun, Weekday::Mon), 6);
+    assert_eq!(TestClass::weekday_to_register(Weekday::Sun, Weekday::Sun), 0);
+    assert_eq!(TestClass::weekday_to_register(Weekday::Sat, Weekday::Sun), 6);
+    assert_eq!(TestClass::register_to_weekday(0, Weekday::Sun), Some(Weekday::Sun));
+    assert_eq!(TestClass::register_to_weekday(1, Weekday::Sun), Some(Weekday::Mon));
+    assert_eq!(TestClass::register_to_weekday(7, Weekday::Mon), None);
+
+    // round trip with every base
+    for base in 0..7u8 {
+      let base = Weekday::try_from(base).unwrap();
+      for val in 0..7u8 {
+        let wd = TestClass::register_to_weekday(val, base).unwrap();
+        assert_eq!(TestClass::weekday_to_register(wd, base), val);
+      }
+    }
+  }
+
+  #[test]
+  fn test_set_alarm_restores_aie() {
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let eie = RegControl2Bits::EventIntEnableBit as u8;
+    let af = RegStatusBits::AlarmFlagBit as u8;
+    let wada = RegControl1Bits::WadaBit as u8;
+    let expectations = [
+      // AIE enabled: disabled while reprogramming
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![aie | eie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, eie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![af]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, wada]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_MINUTES_ALARM, 0x30]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS_ALARM, 0x14]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM, ALARM_NO_WATCH_FLAG]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      // then restored
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, aie | eie]),
+
+      // AIE disabled: left disabled
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, wada]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_MINUTES_ALARM, 0x30]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS_ALARM, 0x14]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM, ALARM_NO_WATCH_FLAG]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_alarm_raw(Some(30), Some(14), None, false).unwrap();
+    rv3028.set_alarm_raw(Some(30), Some(14), None, false).unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_alarm_twelve_hour_mode() {
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let wada = RegControl1Bits::WadaBit as u8;
+    let pm_3 = HOURS_PM_FLAG | 0x03;
+    let expectations = [
+      // set: the hour is written as 3 PM
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, wada]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_MINUTES_ALARM, 0x30]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS_ALARM, pm_3]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM, ALARM_NO_WATCH_FLAG | 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      // read back as 15:30
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM],
+                           vec![0x30, pm_3, ALARM_NO_WATCH_FLAG | 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada, twelve_hour]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM], vec![ALARM_NO_WATCH_FLAG | 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS_ALARM], vec![pm_3]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], vec![0x30]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+      // and accepted as valid
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM],
+                           vec![0x30, pm_3, ALARM_NO_WATCH_FLAG | 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let alarm_dt = NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(15, 30, 0).unwrap();
+    rv3028.set_alarm(&alarm_dt, None, false, true, true).unwrap();
+    assert_eq!(rv3028.get_alarm_config().unwrap(), AlarmConfig {
+      minute: Some(30), hour: Some(15), weekday_or_date: None, is_weekday: false,
+    });
+    let (dt, _weekday, _match_day, match_hour, _match_minute) =
+      rv3028.get_alarm_datetime_wday_matches().unwrap();
+    assert!(match_hour);
+    assert_eq!((dt.hour(), dt.minute()), (15, 30));
+    assert!(rv3028.validate_alarm_config().unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_normalize_hour_mode() {
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let expectations = [
+      // 12 hour mode: 2 PM, with an unwatched alarm hour of 12 AM
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour | aie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS], vec![HOURS_PM_FLAG | 0x02]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS_ALARM], vec![ALARM_NO_WATCH_FLAG | 0x12]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, aie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS, 0x14]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS_ALARM, ALARM_NO_WATCH_FLAG]),
+      // already in 24 hour mode: nothing to do
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![aie]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.normalize_hour_mode().unwrap();
+    rv3028.normalize_hour_mode().unwrap();
+    mock.done();
+
+    // 12 AM and 12 PM are the edge cases of the conversion
+    assert_eq!(TestClass::hour_from_register(0x12, true), 0);
+    assert_eq!(TestClass::hour_from_register(HOURS_PM_FLAG | 0x12, true), 12);
+    assert_eq!(TestClass::hour_from_register(HOURS_PM_FLAG | 0x11, true), 23);
+  }
+
+  #[test]
+  fn test_set_hour_mode_preserving_time() {
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let expectations = [
+      // 24 hour mode: 13:00, with an alarm at 13:15
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS], vec![0x13]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS_ALARM], vec![0x13]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, twelve_hour]),
+      // both 1 PM
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS, HOURS_PM_FLAG | 0x01]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_HOURS_ALARM, HOURS_PM_FLAG | 0x01]),
+      // the displayed time now reads as 1 PM
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                           vec![0x00, 0x00, HOURS_PM_FLAG | 0x01, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x00]),
+      // and the alarm hour is still 13
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM],
+                           vec![0x15, HOURS_PM_FLAG | 0x01, ALARM_NO_WATCH_FLAG]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00, twelve_hour]),
+      // already in 12 hour mode: nothing to do
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_hour_mode_preserving_time(true).unwrap();
+    assert_eq!(rv3028.get_datetime_atomic().unwrap(),
+               NaiveDate::from_ymd_opt(2023, 11, 10).unwrap().and_hms_opt(13, 0, 0).unwrap());
+    assert_eq!(rv3028.get_alarm_config().unwrap().hour, Some(13));
+    rv3028.set_hour_mode_preserving_time(true).unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_read_muxed_times() {
+    let mux_addr = 0x70;
+    let expectations = [
+      I2cTrans::write(mux_addr, vec![0b0000_0001]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], vec![0x01, 0x00, 0x00, 0x00]),
+      I2cTrans::write(mux_addr, vec![0b1000_0000]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], vec![0x02, 0x01, 0x00, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let times = read_muxed_times(&mut mock, mux_addr, &[0b0000_0001, 0b1000_0000]).unwrap();
+    assert_eq!(times, [1, 0x0102]);
+    mock.done();
+  }
+
+  #[test]
+  fn test_alarm_value_out_of_range() {
+    // chrono won't construct an hour of 25, so exercise the alarm register validation directly
+    assert!(matches!(TestClass::alarm_register_value(25, 0, 23, true), Err(Error::OutOfRange)));
+    assert!(matches!(TestClass::alarm_register_value(60, 0, 59, true), Err(Error::OutOfRange)));
+    assert!(matches!(TestClass::alarm_register_value(0, 1, 31, true), Err(Error::OutOfRange)));
+
+    assert_eq!(TestClass::alarm_register_value(23, 0, 23, true).unwrap(), 0x23);
+    assert_eq!(TestClass::alarm_register_value(59, 0, 59, false).unwrap(),
+               ALARM_NO_WATCH_FLAG | 0x59);
+  }
+
+  #[test]
+  fn test_get_datetime_atomic_rollover() {
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      // seconds tick over during the read: 2023-12-31 23:59:59 -> 2024-01-01 00:00:00
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                           vec![0x59, 0x59, 0x23, 0x00, 0x31, 0x12, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x00]),
+      // retry reads the consistent, rolled-over time
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                           vec![0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x24]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_datetime_atomic().unwrap(),
+               NaiveDate::from_ymd_opt(2024, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_countdown_fired_repeat_race() {
+    let tf = RegStatusBits::PeriodicTimerFlag as u8;
+    let expectations = [
+      // counting down, not yet fired
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![0x05, 0x00]),
+      // fires just after the status register is read: timer reloaded
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![0xF0, 0x0F]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, !tf]),
+      // the same countdown isn't reported twice
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![0xE0, 0x0F]),
+      // fires again, seen by TF
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![tf]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, !tf]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![0x10, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert!(!rv3028.countdown_fired_since_last_check().unwrap());
+    assert!(rv3028.countdown_fired_since_last_check().unwrap());
+    assert!(!rv3028.countdown_fired_since_last_check().unwrap());
+    assert!(rv3028.countdown_fired_since_last_check().unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_eeprom_write_disables_refresh() {
+    let eerd = RegControl1Bits::EepromRefreshDisableBit as u8;
+    let eebusy = RegStatusBits::EepromBusyBit as u8;
+    let expectations = [
+      // EERD set before accessing EEPROM
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, eerd]),
+      // wait for any automatic refresh to finish
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![eebusy]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EEPROM_ADDRESS, 0x05]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EEPROM_DATA, 0xAB]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EEPROM_COMMAND, 0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EEPROM_COMMAND, EEPROM_CMD_WRITE_ONE]),
+      // wait for the write to finish
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![eebusy]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      // EERD cleared after the write
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![eerd]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.eeprom_write(0x05, 0xAB).unwrap();
+    mock.done();
+
+    // invalid addresses are rejected before touching the bus
+    assert!(matches!(rv3028.eeprom_write(0x2B, 0xAB), Err(Error::OutOfRange)));
+  }
+
+  #[test]
+  fn test_event_filter_round_trip() {
+    let ehl = RegEventControlBits::EventHighLowBit as u8;
+    let tsow = RegEventControlBits::TimeStampOverwriteBit as u8;
+    // ET bits are bits 5:4 of the Event Control register
+    let variants = [
+      (EventFilterTime::NoFilter, 0x00),
+      (EventFilterTime::Ms3_9, 0x10),
+      (EventFilterTime::Ms15_6, 0x20),
+      (EventFilterTime::Ms125, 0x30),
+    ];
+    for (filter, et_bits) in variants {
+      let expectations = [
+        // other bits are preserved, EHL is cleared for low level
+        I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![ehl | 0x30 | tsow]),
+        I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, et_bits | tsow]),
+        I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![et_bits | tsow]),
+      ];
+      let mut mock = I2cMock::new(&expectations);
+      let mut rv3028 = RV3028::new(mock.clone());
+      rv3028.set_event_trigger(EventTrigger::LowLevel(filter)).unwrap();
+      assert_eq!(rv3028.get_event_filter().unwrap(), filter);
+      mock.done();
+    }
+  }
+
+  #[test]
+  fn test_ext_event_filtering_out_of_range() {
+    // rejected before touching any registers
+    let mut mock = I2cMock::new(&[]);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.config_ext_event_detection(true, false, 5, false), Err(Error::OutOfRange));
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_event_log_source() {
+    let tss = RegEventControlBits::TimeStampSourceBit as u8;
+    let tsow = RegEventControlBits::TimeStampOverwriteBit as u8;
+    let expectations = [
+      // backup switchover source, logging the latest event
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![tss | tsow]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      // EVI source, logging the first event
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let log = rv3028.get_event_log().unwrap();
+    assert_eq!(log.source, TS_EVENT_SOURCE_BSF);
+    assert!(log.is_latest);
+    assert_eq!(log.count, 1);
+    assert_eq!(log.timestamp, NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(12, 30, 15));
+    let log = rv3028.get_event_log().unwrap();
+    assert_eq!(log.source, TS_EVENT_SOURCE_EVI);
+    assert!(!log.is_latest);
+    assert_eq!(log.timestamp, None);
+    mock.done();
+  }
+
+  #[test]
+  fn test_event_timestamp_invalid_bcd() {
+    let expectations = [
+      // count, then seconds, minutes, hours, date, month (invalid 0x13), year
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x02, 0x15, 0x30, 0x12, 0x05, 0x13, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x02, 0x15, 0x30, 0x12, 0x05, 0x13, 0x23]),
+      // invalid BCD digit in the minutes register
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x3A, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x3A, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap(), (2, None));
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap(), (1, None));
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap(),
+               (1, NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(12, 30, 15)));
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_alarm_date_zero() {
+    let expectations = [
+      // unmatched date alarm holding a date of 0
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM], vec![ALARM_NO_WATCH_FLAG]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS_ALARM], vec![0x13]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], vec![0x45]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![RegControl1Bits::WadaBit as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let (dt, weekday, match_day, match_hour, match_minute) =
+      rv3028.get_alarm_datetime_wday_matches().unwrap();
+    assert_eq!(dt.day(), NaiveDateTime::UNIX_EPOCH.day());
+    assert_eq!((dt.hour(), dt.minute()), (13, 45));
+    assert_eq!(weekday, None);
+    assert_eq!((match_day, match_hour, match_minute), (false, true, true));
+    mock.done();
+  }
+
+  #[test]
+  fn test_measure_write_latency() {
+    let unix_start = 1_700_000_000u32;
+    let bytes = unix_start.to_le_bytes();
+    let mut expectations = vec![
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      // starting Unix time and time of day, 12:30:10
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x10, 0x30, 0x12]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], bytes.to_vec()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x10]),
+    ];
+    for _ in 0..3 {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], bytes.to_vec()));
+      expectations.push(I2cTrans::write(RV3028_ADDRESS, [&[REG_UNIX_TIME_0], &bytes[..]].concat()));
+    }
+    // a tick landed between a read and its write-back: the counter lost a second
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x11, 0x30, 0x12]));
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], bytes.to_vec()));
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x11]));
+    let restored = (unix_start + 1).to_le_bytes();
+    expectations.push(I2cTrans::write(RV3028_ADDRESS, [&[REG_UNIX_TIME_0], &restored[..]].concat()));
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x11, 0x30, 0x12]));
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], restored.to_vec()));
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x11]));
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    // fake clock that advances 250 units per call
+    let now = core::cell::Cell::new(0u64);
+    let clock = || { now.set(now.get() + 250); now.get() };
+    assert_eq!(rv3028.measure_write_latency(3, clock).unwrap(), 250);
+    assert_eq!(rv3028.measure_write_latency(0, clock).unwrap(), 0);
+    mock.done();
+  }
+
+  #[test]
+  fn test_await_countdown_period() {
+    let tf = RegStatusBits::PeriodicTimerFlag as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![tf]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![tf]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      // never fires
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    // fake clock that advances 1000 microseconds per call
+    let now = core::cell::Cell::new(0i64);
+    let clock = || { now.set(now.get() + 1000); now.get() };
+    assert_eq!(rv3028.await_countdown_period(clock, 5).unwrap(), Duration::milliseconds(1));
+    assert_eq!(rv3028.await_countdown_period(clock, 2), Err(Error::Timeout));
+    mock.done();
+  }
+
+  #[test]
+  fn test_set_interrupt_enables() {
+    let uie = RegControl2Bits::TimeUpdateIntEnableBit as u8;
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let bsie = RegEepromMirrorBits::BackupSwitchIntEnableBit as u8;
+    let expectations = [
+      // UIE swapped for AIE, other Control 2 bits preserved
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![uie | twelve_hour]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, aie | twelve_hour]),
+      // BSIE already enabled: no write
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_MIRROR_ADDRESS], vec![bsie | 0x01]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_interrupt_enables(InterruptEnables {
+      alarm: true,
+      backup_switchover: true,
+      ..Default::default()
+    }).unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_unix_time_with_retries() {
+    let before = 1_700_000_000u32.to_le_bytes().to_vec();
+    let after = 1_700_000_001u32.to_le_bytes().to_vec();
+    let expectations = [
+      // counter increments between the first pair of reads
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], before),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], after.clone()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], after.clone()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], after),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_unix_time_with_retries().unwrap(), (1_700_000_001, 1));
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_oscillator_status() {
+    let lsb = RegEepromMirrorBits::OffsetLsbBit as u8;
+    let tce = RegEepromMirrorBits::TrickleChargeEnableBit as u8;
+    let expectations = [
+      // factory default: zero offset
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_OFFSET_ADDRESS], vec![0x00, tce]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_OFFSET_ADDRESS], vec![0x02, lsb | tce]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_OFFSET_ADDRESS], vec![0xFF, lsb]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_OFFSET_ADDRESS], vec![0x80, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_oscillator_status().unwrap().aging_offset, 0);
+    assert_eq!(rv3028.get_oscillator_status().unwrap().aging_offset, 5);
+    assert_eq!(rv3028.get_oscillator_status().unwrap().aging_offset, -1);
+    assert_eq!(rv3028.get_oscillator_status().unwrap().aging_offset, -256);
+    mock.done();
+  }
+
+  #[test]
+  fn test_set_alarm_match_flags() {
+    let wada = RegControl1Bits::WadaBit as u8;
+    let expectations = [
+      // daily alarm at 14:30 on the 5th...
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM],
+                           vec![0x30, 0x14, ALARM_NO_WATCH_FLAG | 0x05]),
+      // ...becomes a monthly alarm
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_MINUTES_ALARM, 0x30, 0x14, 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], vec![0x30, 0x14, 0x05]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_alarm_match_flags(true, true, true).unwrap();
+    assert_eq!(rv3028.get_alarm_config().unwrap(), AlarmConfig {
+      minute: Some(30), hour: Some(14), weekday_or_date: Some(5), is_weekday: false,
+    });
+    assert_eq!(rv3028.set_alarm_match_flags(false, false, false), Err(Error::AlarmMatchesNothing));
+    mock.done();
+  }
+
+  #[test]
+  fn test_time_until_countdown_expiry() {
+    let te = RegControl1Bits::TimerEnableBit as u8;
+    let expectations = [
+      // 64 Hz, 100 ticks remaining
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![te | TimerClockFreq::Hertz64 as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![100, 0x00]),
+      // 4096 Hz, 4095 ticks remaining
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![te | TimerClockFreq::Hertz4096 as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_TIMER_STATUS0], vec![0xFF, 0x0F]),
+      // timer disabled
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![TimerClockFreq::Hertz1 as u8]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.time_until_countdown_expiry().unwrap(), Some(Duration::milliseconds(1562) + Duration::microseconds(500)));
+    assert_eq!(rv3028.time_until_countdown_expiry().unwrap(), Some(Duration::nanoseconds(999_755_859)));
+    assert_eq!(rv3028.time_until_countdown_expiry().unwrap(), None);
+    mock.done();
+  }
+
+  #[test]
+  fn test_latest_counter_slot() {
+    // factory fresh EEPROM
+    assert_eq!(TestClass::latest_counter_slot(&[0, 0, 0]), 0);
+    assert_eq!(TestClass::latest_counter_slot(&[0, 1, 0]), 1);
+    assert_eq!(TestClass::latest_counter_slot(&[0, 1, 2]), 2);
+    // wrapped around the ring
+    assert_eq!(TestClass::latest_counter_slot(&[3, 1, 2]), 0);
+    // sequence byte wraps too
+    assert_eq!(TestClass::latest_counter_slot(&[0, 254, 255]), 0);
+    assert_eq!(TestClass::latest_counter_slot(&[7]), 0);
+  }
+
+  #[test]
+  fn test_reset_timestamp_log_clears_count() {
+    let tsr = RegEventControlBits::TimeStampResetBit as u8;
+    let tsow = RegEventControlBits::TimeStampOverwriteBit as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x03, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x03, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      // TSR is the only reset, and it covers the count and the time stamp
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![tsow]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, tsow | tsr]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap().0, 3);
+    rv3028.reset_timestamp_log().unwrap();
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap(), (0, None));
+    mock.done();
+  }
+
+  #[test]
+  fn test_periodic_time_update_suppresses_spurious_flag() {
+    let uie = RegControl2Bits::TimeUpdateIntEnableBit as u8;
+    let uf = RegStatusBits::TimeUpdateFlag as u8;
+    let usel = RegControl1Bits::UselBit as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, usel]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, uie]),
+      // an update occurred while reconfiguring
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![uf]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![uf]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert!(rv3028.configure_periodic_time_update(true, true).unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_validate_alarm_config() {
+    let wada = RegControl1Bits::WadaBit as u8;
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let mut expectations = Vec::new();
+    let mut expect = |regs: Vec<u8>, control1: u8, control2: u8| {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], regs));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![control1]));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![control2]));
+    };
+    // date alarm on the 31st at 14:30
+    expect(vec![0x30, 0x14, 0x31], wada, 0);
+    // weekday mode, with a date left in the weekday register
+    expect(vec![0x30, 0x14, 0x31], 0, 0);
+    // nothing matched
+    expect(vec![ALARM_NO_WATCH_FLAG, ALARM_NO_WATCH_FLAG, ALARM_NO_WATCH_FLAG], wada, 0);
+    // 12 PM in 12 hour mode, weekday ignored
+    expect(vec![0x00, HOURS_PM_FLAG | 0x12, ALARM_NO_WATCH_FLAG | 0x31], 0, twelve_hour);
+    // invalid BCD minutes
+    expect(vec![0x3A, 0x14, ALARM_NO_WATCH_FLAG], wada, 0);
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert!(rv3028.validate_alarm_config().unwrap());
+    assert!(!rv3028.validate_alarm_config().unwrap());
+    assert!(!rv3028.validate_alarm_config().unwrap());
+    assert!(rv3028.validate_alarm_config().unwrap());
+    assert!(!rv3028.validate_alarm_config().unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_route_countdown_to_clockout() {
+    let clkoe = RegEepromClkoutBits::ClockoutEnableBit as u8;
+    let porie = RegEepromClkoutBits::PorIntEnableBit as u8;
+    let ceie = RegClockIntMaskBits::ClockoutOnExtEvtBit as u8;
+    let ctie = RegClockIntMaskBits::ClockoutOnPctBit as u8;
+    let expectations = [
+      // 1 Hz: switched to the countdown timer, PORIE preserved
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS],
+                           vec![porie | ClockoutRate::Hertz1 as u8]),
+      I2cTrans::write(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS,
+                                           clkoe | porie | ClockoutRate::CountdownTimer as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![ceie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK, ceie | ctie]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.route_countdown_to_clockout().unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_alarm_unmatched_placeholders() {
+    let expectations = [
+      // weekday alarm at minute 45, with stale weekday and hour values
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_WEEKDAY_DATE_ALARM], vec![ALARM_NO_WATCH_FLAG | 0x03]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_HOURS_ALARM], vec![ALARM_NO_WATCH_FLAG | 0x13]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], vec![0x45]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let (dt, weekday, match_day, match_hour, match_minute) =
+      rv3028.get_alarm_datetime_wday_matches().unwrap();
+    assert_eq!(dt, NaiveDateTime::UNIX_EPOCH.with_minute(45).unwrap());
+    assert_eq!(weekday, None);
+    assert_eq!((match_day, match_hour, match_minute), (false, false, true));
+    mock.done();
+  }
+
+  #[test]
+  fn test_self_test() {
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_ID], vec![0x33]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0x12, 0x34]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0x55, 0xAA]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0x55, 0xAA]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0xAA, 0x55]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0xAA, 0x55]),
+      // original contents restored
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0x12, 0x34]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      // floating bus
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_ID], vec![0xFF]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.self_test().unwrap();
+    assert_eq!(rv3028.self_test(), Err(Error::VerifyFailed));
+    mock.done();
+  }
+
+  #[test]
+  fn test_set_unix_time_from_datetime() {
+    let dt = NaiveDate::from_ymd_opt(2023, 12, 10).unwrap().and_hms_opt(14, 30, 0).unwrap().and_utc();
+    let bytes = (dt.timestamp() as u32).to_le_bytes();
+    let expectations = [
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_UNIX_TIME_0, bytes[0], bytes[1], bytes[2], bytes[3]]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.set_unix_time_from_datetime(&dt).unwrap();
+    let before_1970 = NaiveDate::from_ymd_opt(1969, 12, 31).unwrap().and_hms_opt(23, 59, 59).unwrap().and_utc();
+    assert_eq!(rv3028.set_unix_time_from_datetime(&before_1970), Err(Error::OutOfRange));
+    let after_2106 = NaiveDate::from_ymd_opt(2107, 1, 1).unwrap().and_hms_opt(0, 0, 0).unwrap().and_utc();
+    assert_eq!(rv3028.set_unix_time_from_datetime(&after_2106), Err(Error::OutOfRange));
+    mock.done();
+  }
+
+  #[test]
+  fn test_upcoming_alarms() {
+    let wada = RegControl1Bits::WadaBit as u8;
+    // Friday 10 Nov 2023, 10:20:30
+    let time_regs = vec![0x30, 0x20, 0x10, 0x04, 0x10, 0x11, 0x23];
+    let mut expectations = Vec::new();
+    for alarm_regs in [
+      // hourly, at 15 minutes past
+      vec![0x15, ALARM_NO_WATCH_FLAG, ALARM_NO_WATCH_FLAG],
+      // never, on the 32nd
+      vec![0x15, 0x10, 0x32],
+    ] {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], time_regs.clone()));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![time_regs[0]]));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_MINUTES_ALARM], alarm_regs));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![wada, 0x00]));
+    }
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let mut out = [NaiveDateTime::UNIX_EPOCH; 4];
+    assert_eq!(rv3028.upcoming_alarms(3, &mut out).unwrap(), 3);
+    let date = NaiveDate::from_ymd_opt(2023, 11, 10).unwrap();
+    assert_eq!(out[..3], [
+      date.and_hms_opt(11, 15, 0).unwrap(),
+      date.and_hms_opt(12, 15, 0).unwrap(),
+      date.and_hms_opt(13, 15, 0).unwrap(),
+    ]);
+    assert_eq!(rv3028.upcoming_alarms(3, &mut out).unwrap(), 0);
+    mock.done();
+  }
+
+  #[test]
+  fn test_low_power_outputs_round_trip() {
+    let clkoe = RegEepromClkoutBits::ClockoutEnableBit as u8;
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let tse = RegControl2Bits::TimeStampEnableBit as u8;
+    let clkout_rate = ClockoutRate::Hertz1 as u8;
+    let expectations = [
+      // enter: only registers with output bits set are written
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![aie | twelve_hour]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, twelve_hour]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS], vec![clkoe | clkout_rate]),
+      I2cTrans::write(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS, clkout_rate]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_MIRROR_ADDRESS], vec![0x00]),
+      // restore: only the saved bits are set again, other changes are kept
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour | tse]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, aie | twelve_hour | tse]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS], vec![clkout_rate]),
+      I2cTrans::write(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS, clkoe | clkout_rate]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.enter_low_power_outputs().unwrap();
+    rv3028.restore_outputs().unwrap();
+    // nothing left to restore
+    rv3028.restore_outputs().unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_active_power_features() {
+    let control2 = RegControl2Bits::AlarmIntEnableBit as u8 | RegControl2Bits::TwelveHourModeBit as u8;
+    let clkout = RegEepromClkoutBits::ClockoutEnableBit as u8 | ClockoutRate::Hertz1 as u8;
+    let backup = RegEepromMirrorBits::TrickleChargeEnableBit as u8 |
+      RegEepromMirrorBits::BackupSwitchoverDsm as u8 | TrickleChargeCurrentLimiter::Ohms9k as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![control2]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS], vec![clkout, 0x00, backup]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    let features = rv3028.active_power_features().unwrap();
+    assert_eq!(features, PowerFeatures {
+      clockout_enabled: true,
+      clockout_interrupt_controlled: false,
+      clockout_rate: ClockoutRate::Hertz1,
+      trickle_charge_enabled: true,
+      trickle_charge_limiter: TrickleChargeCurrentLimiter::Ohms9k,
+      backup_switchover_enabled: true,
+      interrupts: InterruptEnables { alarm: true, ..Default::default() },
+    });
+    mock.done();
+  }
+
+  #[test]
+  fn test_probe_write_protection() {
+    let expectations = [
+      // unprotected: the probe value is written, then the original restored
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0x5A]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0xA5]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0xA5]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0x5A]),
+      // protected: the probe write doesn't take effect, nothing to restore
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0x5A]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_USER_RAM1, 0xA5]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_USER_RAM1], vec![0x5A]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert!(!rv3028.probe_write_protection().unwrap());
+    assert!(rv3028.probe_write_protection().unwrap());
+    mock.done();
+  }
+
+  #[test]
+  fn test_event_timestamp_torn_read() {
+    let expectations = [
+      // event logged between the first and second reads: the seconds and count changed
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x01, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x02, 0x42, 0x30, 0x12, 0x05, 0x11, 0x23]),
+      // stable from here on
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x02, 0x42, 0x31, 0x12, 0x05, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           vec![0x02, 0x42, 0x31, 0x12, 0x05, 0x11, 0x23]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap(),
+               (2, NaiveDate::from_ymd_opt(2023, 11, 5).unwrap().and_hms_opt(12, 31, 42)));
+    mock.done();
+  }
+
+  #[test]
+  fn test_config_alarm_wake() {
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let clkie = RegControl2Bits::ClockoutIntEnableBit as u8;
+    let twelve_hour = RegControl2Bits::TwelveHourModeBit as u8;
+    let clkoe = RegEepromClkoutBits::ClockoutEnableBit as u8;
+    let caie = RegClockIntMaskBits::ClockoutOnAlarmBit as u8;
+    let ctie = RegClockIntMaskBits::ClockoutOnPctBit as u8;
+    let expectations = [
+      // interrupt and clock output on alarm
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS], vec![clkoe]),
+      I2cTrans::write(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS, ClockoutRate::Hertz1 as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![ctie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK, ctie | caie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, twelve_hour | aie | clkie]),
+      // neither
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![twelve_hour | aie | clkie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![ctie | caie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK, ctie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, twelve_hour | clkie]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.config_alarm_wake(true, Some(ClockoutRate::Hertz1)).unwrap();
+    rv3028.config_alarm_wake(false, None).unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_control_config() {
+    let control1 = RegControl1Bits::TimerRepeatBit as u8 | RegControl1Bits::TimerEnableBit as u8 |
+      TimerClockFreq::Hertz64 as u8;
+    let control2 = RegControl2Bits::TimeStampEnableBit as u8 | RegControl2Bits::AlarmIntEnableBit as u8 |
+      RegControl2Bits::TwelveHourModeBit as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![control1, control2]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_control_config().unwrap(), ControlConfig {
+      timer_repeating: true,
+      date_alarm: false,
+      minute_update: false,
+      eeprom_refresh_disabled: false,
+      timer_enabled: true,
+      timer_clock_freq: TimerClockFreq::Hertz64,
+      timestamp_enabled: true,
+      clockout_int_enabled: false,
+      time_update_int_enabled: false,
+      timer_int_enabled: false,
+      alarm_int_enabled: true,
+      event_int_enabled: false,
+      twelve_hour: true,
+    });
+    mock.done();
+  }
+
+  #[test]
+  fn test_get_clockout_rate() {
+    let clkoe = RegEepromClkoutBits::ClockoutEnableBit as u8;
+    let porie = RegEepromClkoutBits::PorIntEnableBit as u8;
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS],
+                           vec![clkoe | porie | ClockoutRate::Hertz64 as u8]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![EEPROM_CLKOUT_ADDRESS],
+                           vec![clkoe | porie | ClockoutRate::Hertz64 as u8]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_clockout_register().unwrap(), clkoe | porie | 0b011);
+    assert_eq!(rv3028.get_clockout_rate().unwrap(), ClockoutRate::Hertz64);
+    mock.done();
+  }
+
+  #[test]
+  fn test_start_event_capture_keeps_int_enables() {
+    let eie = RegControl2Bits::EventIntEnableBit as u8;
+    let tse = RegControl2Bits::TimeStampEnableBit as u8;
+    let ehl = RegEventControlBits::EventHighLowBit as u8;
+    let tsow = RegEventControlBits::TimeStampOverwriteBit as u8;
+    let tsr = RegEventControlBits::TimeStampResetBit as u8;
+    let expectations = [
+      // EIE enabled, CEIE disabled
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![0x00]),
+      // event detection: EIE is paused while configuring
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, ehl]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![ehl]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, ehl]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CLOCK_INTERRUPT_MASK, 0x00]),
+      // ...then EIE is restored
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, eie]),
+      // time stamp logging
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, eie]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![ehl]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, ehl]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![ehl]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, ehl | tsow]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_EVENT_CONTROL], vec![ehl | tsow]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_EVENT_CONTROL, ehl | tsow | tsr]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![eie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, eie | tse]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.start_event_capture(true, EventFilterTime::NoFilter, true).unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_align_to_external_pps_keeps_tick() {
+    let unix = |val: u32| val.to_le_bytes().to_vec();
+    let expectations = [
+      // no tick: the Seconds value is written back unchanged
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x42]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x42]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      // tick around the Seconds read: it's read again
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x42]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1001)),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x43]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x43]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1001)),
+      // tick between the read and the write: the lost second is restored,
+      // the RTC has already carried into the minutes
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x59]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1000)),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x59]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix(1001)),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x00]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    rv3028.align_to_external_pps().unwrap();
+    rv3028.align_to_external_pps().unwrap();
+    rv3028.align_to_external_pps().unwrap();
+    mock.done();
+  }
+
+  #[test]
+  fn test_apply_drift_correction_steps_both_time_bases() {
+    let unix_time: u32 = 1_699_618_830; // 2023-11-10 12:20:30
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                           vec![0x30, 0x20, 0x12, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x30]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix_time.to_le_bytes().to_vec()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix_time.to_le_bytes().to_vec()),
+      // 2 seconds ahead: both step back to 12:20:28
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x28, 0x20, 0x12]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix_time.to_le_bytes().to_vec()),
+      I2cTrans::write(RV3028_ADDRESS,
+                      [&[REG_UNIX_TIME_0][..], &(unix_time - 2).to_le_bytes()[..]].concat()),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.apply_drift_correction(2).unwrap(), unix_time - 2);
+    mock.done();
+  }
+
+  #[test]
+  fn test_apply_drift_correction_tick_before_write() {
+    let unix_time: u32 = 1_699_618_830; // 2023-11-10 12:20:30
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                           vec![0x30, 0x20, 0x12, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x30]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix_time.to_le_bytes().to_vec()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], unix_time.to_le_bytes().to_vec()),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x28, 0x20, 0x12]),
+      // the RTC ticked to 12:20:31 before Seconds was written
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], (unix_time + 1).to_le_bytes().to_vec()),
+      // so both are corrected from the later time instead: 12:20:29
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_WEEKDAY, 0x04, 0x10, 0x11, 0x23]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_SECONDS, 0x29, 0x20, 0x12]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_UNIX_TIME_0], (unix_time + 1).to_le_bytes().to_vec()),
+      I2cTrans::write(RV3028_ADDRESS,
+                      [&[REG_UNIX_TIME_0][..], &(unix_time - 1).to_le_bytes()[..]].concat()),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.apply_drift_correction(2).unwrap(), unix_time - 1);
+    mock.done();
+  }
+
+  #[test]
+  fn test_estimate_second_phase() {
+    let mut expectations = Vec::new();
+    // ticks after the 2nd and 5th polls: 3 polls per second
+    for seconds in [0x10, 0x10, 0x11, 0x11, 0x11, 0x12] {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![seconds]));
+    }
+    // too few polls to see two ticks
+    for seconds in [0x12, 0x12, 0x13] {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![seconds]));
+    }
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.estimate_second_phase(6).unwrap(), 333_334);
+    assert_eq!(rv3028.estimate_second_phase(3), Err(Error::Timeout));
+    mock.done();
+  }
+
+  #[test]
+  fn test_consistent_reads_time_out() {
+    let mut expectations = vec![];
+    // event timestamp registers change on every read
+    for i in 0..MAX_CONSISTENT_READS {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                                             vec![i as u8, 0x15, 0x30, 0x12, 0x05, 0x11, 0x23]));
+    }
+    // seconds register changes between every pair of reads
+    expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]));
+    for _ in 0..MAX_CONSISTENT_READS {
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS],
+                                             vec![0x10, 0x30, 0x12, 0x00, 0x05, 0x11, 0x23]));
+      expectations.push(I2cTrans::write_read(RV3028_ADDRESS, vec![REG_SECONDS], vec![0x11]));
+    }
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_event_count_and_datetime(), Err(Error::Timeout));
+    assert_eq!(rv3028.get_datetime_atomic(), Err(Error::Timeout));
+    mock.done();
+  }
+
+  #[test]
+  fn test_set_alarm_restores_aie_on_error() {
+    let aie = RegControl2Bits::AlarmIntEnableBit as u8;
+    let bus_error = embedded_hal_mock::MockError::Io(std::io::ErrorKind::Other);
+    let expectations = [
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![aie]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_STATUS], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_STATUS, 0x00]),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL1], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL1, RegControl1Bits::WadaBit as u8]),
+      // the bus fails part way through reprogramming
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_MINUTES_ALARM, 0x30]).with_error(bus_error.clone()),
+      // AIE is still restored
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_CONTROL2], vec![0x00]),
+      I2cTrans::write(RV3028_ADDRESS, vec![REG_CONTROL2, aie]),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.set_alarm_raw(Some(30), None, None, false), Err(Error::I2c(bus_error)));
+    mock.done();
+  }
+
+  #[test]
+  fn test_event_count_is_binary() {
+    let timestamp = [0x15, 0x30, 0x12, 0x05, 0x11, 0x23];
+    let expectations = [
+      // 0x10 would be 10 if the count were BCD
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           [&[0x10], &timestamp[..]].concat()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           [&[0x10], &timestamp[..]].concat()),
+      // 0xFF is not valid BCD: the counter saturates here
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           [&[0xFF], &timestamp[..]].concat()),
+      I2cTrans::write_read(RV3028_ADDRESS, vec![REG_COUNT_EVENTS_TS],
+                           [&[0xFF], &timestamp[..]].concat()),
+    ];
+    let mut mock = I2cMock::new(&expectations);
+    let mut rv3028 = RV3028::new(mock.clone());
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap().0, 16);
+    assert_eq!(rv3028.get_event_count_and_datetime().unwrap().0, 255);
+    mock.done();
+  }
+
 
 
 }